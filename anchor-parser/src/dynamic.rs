@@ -0,0 +1,740 @@
+//! Runtime decoding against an IDL loaded at runtime.
+//!
+//! [`declare_program!`](crate::declare_program) needs the IDL at compile
+//! time. [`Program`] instead interprets an [`Idl`] loaded at runtime,
+//! decoding accounts, instruction data and events into a dynamic [`Value`]
+//! tree. Both Borsh and bytemuck (zero-copy) layouts are supported.
+//!
+//! Values render to JSON with [`Value::to_json`], in the format of Anchor's
+//! TypeScript client.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use anchor_parser::dynamic::Program;
+//!
+//! let program = Program::from_json(&std::fs::read_to_string("idls/my_program.json")?)?;
+//!
+//! let account = program.decode_account(&data)?;
+//! println!("{}: {:?}", account.name, account.value.get("authority"));
+//! ```
+
+mod json;
+
+pub use json::KeyCase;
+
+use std::fmt;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::idl::{
+    Idl, IdlArrayLen, IdlDefinedFields, IdlGenericArg, IdlRepr, IdlSerialization, IdlType,
+    IdlTypeDef, IdlTypeDefGeneric, IdlTypeDefTy,
+};
+use crate::num::{I256, U256};
+
+/// Maximum nesting of type definitions, guarding against recursive aliases.
+const MAX_DEPTH: usize = 64;
+
+/// A dynamically decoded value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    U32(u32),
+    I32(i32),
+    F32(f32),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    U128(u128),
+    I128(i128),
+    U256(U256),
+    I256(I256),
+    String(String),
+    Bytes(Vec<u8>),
+    Pubkey(Pubkey),
+    Option(Option<Box<Value>>),
+    Vec(Vec<Value>),
+    Array(Vec<Value>),
+    Struct(Fields),
+    Enum {
+        /// Variant name.
+        variant: String,
+        fields: Fields,
+    },
+}
+
+/// Fields of a struct or enum variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fields {
+    Unit,
+    Named(Vec<(String, Value)>),
+    Tuple(Vec<Value>),
+}
+
+impl Fields {
+    /// Value of the named field `name`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Fields::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl Value {
+    /// Value of the named field `name` of a struct or enum variant.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct(fields) | Value::Enum { fields, .. } => fields.get(name),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Any unsigned integer that fits in a `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::U8(v) => Some(v.into()),
+            Value::U16(v) => Some(v.into()),
+            Value::U32(v) => Some(v.into()),
+            Value::U64(v) => Some(v),
+            Value::U128(v) => v.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    /// Any signed integer that fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::I8(v) => Some(v.into()),
+            Value::I16(v) => Some(v.into()),
+            Value::I32(v) => Some(v.into()),
+            Value::I64(v) => Some(v),
+            Value::I128(v) => v.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    /// Any unsigned integer.
+    pub fn as_u128(&self) -> Option<u128> {
+        match *self {
+            Value::U128(v) => Some(v),
+            _ => self.as_u64().map(Into::into),
+        }
+    }
+
+    pub fn as_pubkey(&self) -> Option<&Pubkey> {
+        match self {
+            Value::Pubkey(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// An account, instruction or event decoded by [`Program`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoded {
+    /// Name of the account, instruction or event in the IDL.
+    pub name: String,
+    /// The decoded data; instruction arguments form a struct.
+    pub value: Value,
+}
+
+/// Error returned by [`Program`]'s decoders.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// No account, instruction or event of the IDL has this discriminator.
+    UnknownDiscriminator,
+    /// The IDL has no item or type definition of this name.
+    NotFound(String),
+    /// The IDL describes a layout the decoder cannot read, e.g. a `Vec` in
+    /// a bytemuck account.
+    Unsupported(String),
+    /// The data ended early or is malformed.
+    InvalidData(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnknownDiscriminator => write!(f, "Unknown discriminator"),
+            DecodeError::NotFound(name) => write!(f, "`{name}` not found in the IDL"),
+            DecodeError::Unsupported(msg) => write!(f, "Unsupported IDL layout: {msg}"),
+            DecodeError::InvalidData(msg) => write!(f, "Invalid data: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+fn unexpected_eof() -> DecodeError {
+    DecodeError::InvalidData("unexpected end of data".to_string())
+}
+
+/// A program whose IDL is interpreted at runtime.
+#[derive(Debug, Clone)]
+pub struct Program {
+    idl: Idl,
+}
+
+impl Program {
+    pub fn new(idl: Idl) -> Self {
+        Self { idl }
+    }
+
+    /// Parse an IDL, converting the legacy (pre-0.30) format if detected.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        Idl::from_json(json).map(Self::new)
+    }
+
+    pub fn idl(&self) -> &Idl {
+        &self.idl
+    }
+
+    /// Program address declared in the IDL, if valid.
+    pub fn program_id(&self) -> Option<Pubkey> {
+        self.idl.address.parse().ok()
+    }
+
+    /// Decode account data (discriminator + payload), identifying the
+    /// account type by its discriminator.
+    ///
+    /// Bytes after the payload are ignored, as accounts are often allocated
+    /// with spare space.
+    pub fn decode_account(&self, data: &[u8]) -> Result<Decoded, DecodeError> {
+        let account = self
+            .idl
+            .accounts
+            .iter()
+            .find(|acc| !acc.discriminator.is_empty() && data.starts_with(&acc.discriminator))
+            .ok_or(DecodeError::UnknownDiscriminator)?;
+        let value = self.decode_type(&account.name, &data[account.discriminator.len()..])?;
+        Ok(Decoded {
+            name: account.name.clone(),
+            value,
+        })
+    }
+
+    /// Decode instruction data (discriminator + Borsh-encoded arguments)
+    /// into a struct of the arguments.
+    pub fn decode_instruction(&self, data: &[u8]) -> Result<Decoded, DecodeError> {
+        let ix = self
+            .idl
+            .instructions
+            .iter()
+            .find(|ix| !ix.discriminator.is_empty() && data.starts_with(&ix.discriminator))
+            .ok_or(DecodeError::UnknownDiscriminator)?;
+        let mut reader = &data[ix.discriminator.len()..];
+        let args = ix
+            .args
+            .iter()
+            .map(|arg| Ok((arg.name.clone(), self.borsh(&arg.ty, &[], &mut reader, 0)?)))
+            .collect::<Result<_, DecodeError>>()?;
+        Ok(Decoded {
+            name: ix.name.clone(),
+            value: Value::Struct(Fields::Named(args)),
+        })
+    }
+
+    /// Decode an event (discriminator + Borsh payload), as found in
+    /// `Program data:` log lines and, after the `EVENT_IX_TAG`, in
+    /// `emit_cpi!` instruction data.
+    pub fn decode_event(&self, data: &[u8]) -> Result<Decoded, DecodeError> {
+        let event = self
+            .idl
+            .events
+            .iter()
+            .find(|ev| !ev.discriminator.is_empty() && data.starts_with(&ev.discriminator))
+            .ok_or(DecodeError::UnknownDiscriminator)?;
+        let value = self.decode_type(&event.name, &data[event.discriminator.len()..])?;
+        Ok(Decoded {
+            name: event.name.clone(),
+            value,
+        })
+    }
+
+    /// Decode every event in `Program data:` log lines, skipping lines that
+    /// are not events of this program.
+    pub fn events_from_logs<T, I>(&self, logs: T) -> Vec<Decoded>
+    where
+        T: IntoIterator<Item = I>,
+        I: AsRef<str>,
+    {
+        logs.into_iter()
+            .filter_map(|log| {
+                let data = log.as_ref().strip_prefix("Program data: ")?;
+                let data = crate::__private::base64_decode(data)?;
+                self.decode_event(&data).ok()
+            })
+            .collect()
+    }
+
+    /// Decode the payload of a type definition, without a discriminator,
+    /// using its Borsh or bytemuck layout.
+    pub fn decode_type(&self, name: &str, data: &[u8]) -> Result<Value, DecodeError> {
+        let ty_def = self.type_def(name)?;
+        if is_bytemuck(ty_def) {
+            let (size, _) = self.pod_type_def_layout(ty_def, &[], 0)?;
+            let data = data.get(..size).ok_or_else(unexpected_eof)?;
+            self.pod_type_def(ty_def, &[], data, 0)
+        } else {
+            self.borsh_type_def(ty_def, &[], &mut &data[..], 0)
+        }
+    }
+
+    fn type_def(&self, name: &str) -> Result<&IdlTypeDef, DecodeError> {
+        self.idl
+            .types
+            .iter()
+            .find(|ty| ty.name == name)
+            .ok_or_else(|| DecodeError::NotFound(name.to_string()))
+    }
+
+    /// Bind a type definition's generic parameters to `args`, resolving the
+    /// arguments against the caller's bindings.
+    fn bind_generics(
+        &self,
+        ty_def: &IdlTypeDef,
+        args: &[IdlGenericArg],
+        env: &[Generic],
+    ) -> Result<Vec<Generic>, DecodeError> {
+        if ty_def.generics.len() != args.len() {
+            return Err(DecodeError::Unsupported(format!(
+                "`{}` expects {} generic arguments, got {}",
+                ty_def.name,
+                ty_def.generics.len(),
+                args.len()
+            )));
+        }
+        ty_def
+            .generics
+            .iter()
+            .zip(args)
+            .map(|(param, arg)| match (param, arg) {
+                (IdlTypeDefGeneric::Type { name }, IdlGenericArg::Type { ty }) => Ok(Generic {
+                    name: name.clone(),
+                    value: GenericValue::Type(substitute(ty, env)?),
+                }),
+                (IdlTypeDefGeneric::Const { name, .. }, IdlGenericArg::Const { value }) => {
+                    let len = value.trim().parse().map_err(|_| {
+                        DecodeError::Unsupported(format!("const generic `{name}` = `{value}`"))
+                    })?;
+                    Ok(Generic {
+                        name: name.clone(),
+                        value: GenericValue::Const(len),
+                    })
+                }
+                _ => Err(DecodeError::Unsupported(format!(
+                    "mismatched generic arguments of `{}`",
+                    ty_def.name
+                ))),
+            })
+            .collect()
+    }
+
+    // ── Borsh ────────────────────────────────────────────────────────
+
+    fn borsh(
+        &self,
+        ty: &IdlType,
+        env: &[Generic],
+        reader: &mut &[u8],
+        depth: usize,
+    ) -> Result<Value, DecodeError> {
+        Ok(match ty {
+            IdlType::Bool => match take::<1>(reader)? {
+                [0] => Value::Bool(false),
+                [1] => Value::Bool(true),
+                [b] => return Err(DecodeError::InvalidData(format!("invalid bool {b}"))),
+            },
+            IdlType::String => {
+                let bytes = take_len_prefixed(reader)?;
+                Value::String(
+                    String::from_utf8(bytes.to_vec())
+                        .map_err(|e| DecodeError::InvalidData(e.to_string()))?,
+                )
+            }
+            IdlType::Bytes => Value::Bytes(take_len_prefixed(reader)?.to_vec()),
+            IdlType::Option(inner) => match take::<1>(reader)? {
+                [0] => Value::Option(None),
+                [1] => Value::Option(Some(Box::new(self.borsh(inner, env, reader, depth)?))),
+                [b] => return Err(DecodeError::InvalidData(format!("invalid option tag {b}"))),
+            },
+            IdlType::Vec(inner) => {
+                let len = u32::from_le_bytes(take(reader)?) as usize;
+                // Every element takes at least one byte, except for
+                // zero-sized ones, which borsh rejects so that a length
+                // prefix alone cannot make the decoder spin
+                let mut items = Vec::with_capacity(len.min(reader.len()));
+                for _ in 0..len {
+                    let remaining = reader.len();
+                    items.push(self.borsh(inner, env, reader, depth)?);
+                    if reader.len() == remaining {
+                        return Err(DecodeError::InvalidData(
+                            "vec of zero-sized elements".to_string(),
+                        ));
+                    }
+                }
+                Value::Vec(items)
+            }
+            IdlType::Array(inner, len) => {
+                let len = array_len(len, env)?;
+                let items = (0..len)
+                    .map(|_| self.borsh(inner, env, reader, depth))
+                    .collect::<Result<_, _>>()?;
+                Value::Array(items)
+            }
+            IdlType::Defined { name, generics } => {
+                let ty_def = self.type_def(name)?;
+                let env = self.bind_generics(ty_def, generics, env)?;
+                self.borsh_type_def(ty_def, &env, reader, depth + 1)?
+            }
+            IdlType::Generic(name) => {
+                let ty = generic_type(name, env)?;
+                self.borsh(&ty, &[], reader, depth)?
+            }
+            _ => primitive(ty, reader)?,
+        })
+    }
+
+    fn borsh_type_def(
+        &self,
+        ty_def: &IdlTypeDef,
+        env: &[Generic],
+        reader: &mut &[u8],
+        depth: usize,
+    ) -> Result<Value, DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::Unsupported(
+                "types nested too deeply".to_string(),
+            ));
+        }
+        match &ty_def.ty {
+            IdlTypeDefTy::Struct { fields } => Ok(Value::Struct(self.borsh_fields(
+                fields.as_ref(),
+                env,
+                reader,
+                depth,
+            )?)),
+            IdlTypeDefTy::Enum { variants } => {
+                let [index] = take::<1>(reader)?;
+                let variant = variants.get(index as usize).ok_or_else(|| {
+                    DecodeError::InvalidData(format!(
+                        "invalid variant {index} of `{}`",
+                        ty_def.name
+                    ))
+                })?;
+                Ok(Value::Enum {
+                    variant: variant.name.clone(),
+                    fields: self.borsh_fields(variant.fields.as_ref(), env, reader, depth)?,
+                })
+            }
+            IdlTypeDefTy::Type { alias } => self.borsh(alias, env, reader, depth),
+        }
+    }
+
+    fn borsh_fields(
+        &self,
+        fields: Option<&IdlDefinedFields>,
+        env: &[Generic],
+        reader: &mut &[u8],
+        depth: usize,
+    ) -> Result<Fields, DecodeError> {
+        Ok(match fields {
+            None => Fields::Unit,
+            Some(IdlDefinedFields::Named(fields)) => Fields::Named(
+                fields
+                    .iter()
+                    .map(|f| Ok((f.name.clone(), self.borsh(&f.ty, env, reader, depth)?)))
+                    .collect::<Result<_, DecodeError>>()?,
+            ),
+            Some(IdlDefinedFields::Tuple(tys)) => Fields::Tuple(
+                tys.iter()
+                    .map(|ty| self.borsh(ty, env, reader, depth))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+
+    // ── Bytemuck ─────────────────────────────────────────────────────
+
+    /// Size and alignment of a type in a bytemuck layout, as compiled for
+    /// the on-chain target, where `u128` / `i128` are 8-byte aligned.
+    fn pod_layout(
+        &self,
+        ty: &IdlType,
+        env: &[Generic],
+        depth: usize,
+    ) -> Result<(usize, usize), DecodeError> {
+        Ok(match ty {
+            IdlType::Bool | IdlType::U8 | IdlType::I8 => (1, 1),
+            IdlType::U16 | IdlType::I16 => (2, 2),
+            IdlType::U32 | IdlType::I32 | IdlType::F32 => (4, 4),
+            IdlType::U64 | IdlType::I64 | IdlType::F64 => (8, 8),
+            IdlType::U128 | IdlType::I128 => (16, 8),
+            IdlType::U256 | IdlType::I256 | IdlType::Pubkey => (32, 1),
+            IdlType::Array(inner, len) => {
+                let (size, align) = self.pod_layout(inner, env, depth)?;
+                (size * array_len(len, env)?, align)
+            }
+            IdlType::Defined { name, generics } => {
+                let ty_def = self.type_def(name)?;
+                let env = self.bind_generics(ty_def, generics, env)?;
+                self.pod_type_def_layout(ty_def, &env, depth + 1)?
+            }
+            IdlType::Generic(name) => self.pod_layout(&generic_type(name, env)?, &[], depth)?,
+            IdlType::String | IdlType::Bytes | IdlType::Option(_) | IdlType::Vec(_) => {
+                return Err(DecodeError::Unsupported(format!(
+                    "{ty:?} in a bytemuck layout"
+                )));
+            }
+        })
+    }
+
+    fn pod_type_def_layout(
+        &self,
+        ty_def: &IdlTypeDef,
+        env: &[Generic],
+        depth: usize,
+    ) -> Result<(usize, usize), DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::Unsupported(
+                "types nested too deeply".to_string(),
+            ));
+        }
+        match &ty_def.ty {
+            IdlTypeDefTy::Struct { fields } => {
+                let (packed, min_align) = repr_modifiers(ty_def);
+                let mut offset = 0usize;
+                let mut align = min_align;
+                for ty in field_types(fields.as_ref()) {
+                    let (field_size, field_align) = self.pod_layout(ty, env, depth)?;
+                    let field_align = if packed { 1 } else { field_align };
+                    offset = offset.next_multiple_of(field_align) + field_size;
+                    align = align.max(field_align);
+                }
+                Ok((offset.next_multiple_of(align), align))
+            }
+            IdlTypeDefTy::Type { alias } => self.pod_layout(alias, env, depth),
+            IdlTypeDefTy::Enum { .. } => Err(DecodeError::Unsupported(format!(
+                "enum `{}` in a bytemuck layout",
+                ty_def.name
+            ))),
+        }
+    }
+
+    /// Decode `data`, which holds exactly the bytes of `ty`.
+    fn pod(
+        &self,
+        ty: &IdlType,
+        env: &[Generic],
+        data: &[u8],
+        depth: usize,
+    ) -> Result<Value, DecodeError> {
+        Ok(match ty {
+            IdlType::Bool => Value::Bool(data[0] != 0),
+            IdlType::Array(inner, len) => {
+                let len = array_len(len, env)?;
+                let (size, _) = self.pod_layout(inner, env, depth)?;
+                let items = (0..len)
+                    .map(|i| self.pod(inner, env, &data[i * size..(i + 1) * size], depth))
+                    .collect::<Result<_, _>>()?;
+                Value::Array(items)
+            }
+            IdlType::Defined { name, generics } => {
+                let ty_def = self.type_def(name)?;
+                let env = self.bind_generics(ty_def, generics, env)?;
+                self.pod_type_def(ty_def, &env, data, depth + 1)?
+            }
+            IdlType::Generic(name) => self.pod(&generic_type(name, env)?, &[], data, depth)?,
+            _ => primitive(ty, &mut &data[..])?,
+        })
+    }
+
+    fn pod_type_def(
+        &self,
+        ty_def: &IdlTypeDef,
+        env: &[Generic],
+        data: &[u8],
+        depth: usize,
+    ) -> Result<Value, DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::Unsupported(
+                "types nested too deeply".to_string(),
+            ));
+        }
+        match &ty_def.ty {
+            IdlTypeDefTy::Struct { fields } => {
+                let (packed, _) = repr_modifiers(ty_def);
+                let mut offset = 0usize;
+                let mut values = Vec::new();
+                for ty in field_types(fields.as_ref()) {
+                    let (size, align) = self.pod_layout(ty, env, depth)?;
+                    offset = offset.next_multiple_of(if packed { 1 } else { align });
+                    values.push(self.pod(ty, env, &data[offset..offset + size], depth)?);
+                    offset += size;
+                }
+                Ok(Value::Struct(match fields {
+                    None => Fields::Unit,
+                    Some(IdlDefinedFields::Named(fields)) => {
+                        Fields::Named(fields.iter().map(|f| f.name.clone()).zip(values).collect())
+                    }
+                    Some(IdlDefinedFields::Tuple(_)) => Fields::Tuple(values),
+                }))
+            }
+            IdlTypeDefTy::Type { alias } => self.pod(alias, env, data, depth),
+            IdlTypeDefTy::Enum { .. } => Err(DecodeError::Unsupported(format!(
+                "enum `{}` in a bytemuck layout",
+                ty_def.name
+            ))),
+        }
+    }
+}
+
+/// A generic parameter bound to a concrete argument.
+#[derive(Debug, Clone)]
+struct Generic {
+    name: String,
+    value: GenericValue,
+}
+
+#[derive(Debug, Clone)]
+enum GenericValue {
+    Type(IdlType),
+    Const(usize),
+}
+
+fn generic_type(name: &str, env: &[Generic]) -> Result<IdlType, DecodeError> {
+    match env.iter().find(|g| g.name == name).map(|g| &g.value) {
+        Some(GenericValue::Type(ty)) => Ok(ty.clone()),
+        _ => Err(DecodeError::Unsupported(format!(
+            "unbound generic type `{name}`"
+        ))),
+    }
+}
+
+fn array_len(len: &IdlArrayLen, env: &[Generic]) -> Result<usize, DecodeError> {
+    match len {
+        IdlArrayLen::Value(len) => Ok(*len),
+        IdlArrayLen::Generic(name) => {
+            match env.iter().find(|g| g.name == *name).map(|g| &g.value) {
+                Some(GenericValue::Const(len)) => Ok(*len),
+                _ => Err(DecodeError::Unsupported(format!(
+                    "unbound generic length `{name}`"
+                ))),
+            }
+        }
+    }
+}
+
+/// Replace the generic parameters in `ty` with their bound arguments.
+fn substitute(ty: &IdlType, env: &[Generic]) -> Result<IdlType, DecodeError> {
+    Ok(match ty {
+        IdlType::Generic(name) => generic_type(name, env)?,
+        IdlType::Option(inner) => IdlType::Option(Box::new(substitute(inner, env)?)),
+        IdlType::Vec(inner) => IdlType::Vec(Box::new(substitute(inner, env)?)),
+        IdlType::Array(inner, len) => IdlType::Array(
+            Box::new(substitute(inner, env)?),
+            IdlArrayLen::Value(array_len(len, env)?),
+        ),
+        IdlType::Defined { name, generics } => IdlType::Defined {
+            name: name.clone(),
+            generics: generics
+                .iter()
+                .map(|arg| {
+                    Ok(match arg {
+                        IdlGenericArg::Type { ty } => IdlGenericArg::Type {
+                            ty: substitute(ty, env)?,
+                        },
+                        IdlGenericArg::Const { value } => {
+                            match env.iter().find(|g| g.name == *value).map(|g| &g.value) {
+                                Some(GenericValue::Const(len)) => IdlGenericArg::Const {
+                                    value: len.to_string(),
+                                },
+                                _ => arg.clone(),
+                            }
+                        }
+                    })
+                })
+                .collect::<Result<_, DecodeError>>()?,
+        },
+        _ => ty.clone(),
+    })
+}
+
+fn is_bytemuck(ty_def: &IdlTypeDef) -> bool {
+    matches!(
+        ty_def.serialization,
+        IdlSerialization::Bytemuck | IdlSerialization::BytemuckUnsafe
+    )
+}
+
+/// `(packed, align)` of a struct's `repr`, defaulting to `repr(C)`.
+fn repr_modifiers(ty_def: &IdlTypeDef) -> (bool, usize) {
+    match &ty_def.repr {
+        Some(IdlRepr::C(m) | IdlRepr::Rust(m)) => (m.packed, m.align.unwrap_or(1)),
+        _ => (false, 1),
+    }
+}
+
+fn field_types(fields: Option<&IdlDefinedFields>) -> Vec<&IdlType> {
+    match fields {
+        None => Vec::new(),
+        Some(IdlDefinedFields::Named(fields)) => fields.iter().map(|f| &f.ty).collect(),
+        Some(IdlDefinedFields::Tuple(tys)) => tys.iter().collect(),
+    }
+}
+
+fn take<const N: usize>(reader: &mut &[u8]) -> Result<[u8; N], DecodeError> {
+    let (bytes, rest) = reader.split_first_chunk().ok_or_else(unexpected_eof)?;
+    *reader = rest;
+    Ok(*bytes)
+}
+
+fn take_len_prefixed<'a>(reader: &mut &'a [u8]) -> Result<&'a [u8], DecodeError> {
+    let len = u32::from_le_bytes(take(reader)?) as usize;
+    let (bytes, rest) = reader.split_at_checked(len).ok_or_else(unexpected_eof)?;
+    *reader = rest;
+    Ok(bytes)
+}
+
+/// Read a fixed-size little-endian primitive.
+fn primitive(ty: &IdlType, reader: &mut &[u8]) -> Result<Value, DecodeError> {
+    Ok(match ty {
+        IdlType::Bool => Value::Bool(take::<1>(reader)?[0] != 0),
+        IdlType::U8 => Value::U8(u8::from_le_bytes(take(reader)?)),
+        IdlType::I8 => Value::I8(i8::from_le_bytes(take(reader)?)),
+        IdlType::U16 => Value::U16(u16::from_le_bytes(take(reader)?)),
+        IdlType::I16 => Value::I16(i16::from_le_bytes(take(reader)?)),
+        IdlType::U32 => Value::U32(u32::from_le_bytes(take(reader)?)),
+        IdlType::I32 => Value::I32(i32::from_le_bytes(take(reader)?)),
+        IdlType::F32 => Value::F32(f32::from_le_bytes(take(reader)?)),
+        IdlType::U64 => Value::U64(u64::from_le_bytes(take(reader)?)),
+        IdlType::I64 => Value::I64(i64::from_le_bytes(take(reader)?)),
+        IdlType::F64 => Value::F64(f64::from_le_bytes(take(reader)?)),
+        IdlType::U128 => Value::U128(u128::from_le_bytes(take(reader)?)),
+        IdlType::I128 => Value::I128(i128::from_le_bytes(take(reader)?)),
+        IdlType::U256 => Value::U256(U256::from_le_bytes(take(reader)?)),
+        IdlType::I256 => Value::I256(I256::from_le_bytes(take(reader)?)),
+        IdlType::Pubkey => Value::Pubkey(Pubkey::new_from_array(take(reader)?)),
+        _ => unreachable!("not a primitive: {ty:?}"),
+    })
+}
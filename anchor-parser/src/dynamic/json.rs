@@ -0,0 +1,107 @@
+//! JSON rendering of dynamically decoded values, matching the output of
+//! Anchor's TypeScript client.
+
+use heck::{ToLowerCamelCase, ToSnakeCase};
+use serde_json::{Map, Number, Value as Json};
+
+use super::{Decoded, Fields, Value};
+
+/// Casing of field names and enum variants in rendered JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyCase {
+    /// `tokenXMint`, `{ "swapExactIn": {} }`, as `@coral-xyz/anchor` renders.
+    #[default]
+    Camel,
+    /// Field names as declared in the IDL (`token_x_mint`), and snake_case
+    /// variants (`{ "swap_exact_in": {} }`).
+    Snake,
+}
+
+impl KeyCase {
+    fn field(self, name: &str) -> String {
+        match self {
+            KeyCase::Camel => name.to_lower_camel_case(),
+            KeyCase::Snake => name.to_string(),
+        }
+    }
+
+    fn variant(self, name: &str) -> String {
+        match self {
+            KeyCase::Camel => name.to_lower_camel_case(),
+            KeyCase::Snake => name.to_snake_case(),
+        }
+    }
+}
+
+impl Value {
+    /// Render as JSON the way Anchor's TypeScript client does:
+    ///
+    /// - 64-bit and wider integers as decimal strings, smaller ones as
+    ///   numbers
+    /// - pubkeys in base58
+    /// - `bytes` and arrays as arrays, `None` as `null`
+    /// - structs as objects; tuple fields are keyed by index (`"0"`, `"1"`)
+    /// - enums as `{ "variantName": { ...fields } }`
+    pub fn to_json(&self, case: KeyCase) -> Json {
+        match self {
+            Value::Bool(v) => Json::Bool(*v),
+            Value::U8(v) => Json::from(*v),
+            Value::I8(v) => Json::from(*v),
+            Value::U16(v) => Json::from(*v),
+            Value::I16(v) => Json::from(*v),
+            Value::U32(v) => Json::from(*v),
+            Value::I32(v) => Json::from(*v),
+            Value::F32(v) => Number::from_f64((*v).into()).map_or(Json::Null, Json::Number),
+            Value::F64(v) => Number::from_f64(*v).map_or(Json::Null, Json::Number),
+            Value::U64(v) => Json::String(v.to_string()),
+            Value::I64(v) => Json::String(v.to_string()),
+            Value::U128(v) => Json::String(v.to_string()),
+            Value::I128(v) => Json::String(v.to_string()),
+            Value::U256(v) => Json::String(v.to_string()),
+            Value::I256(v) => Json::String(v.to_string()),
+            Value::String(v) => Json::String(v.clone()),
+            Value::Bytes(bytes) => Json::Array(bytes.iter().map(|b| Json::from(*b)).collect()),
+            Value::Pubkey(v) => Json::String(v.to_string()),
+            Value::Option(v) => v.as_ref().map_or(Json::Null, |v| v.to_json(case)),
+            Value::Vec(items) | Value::Array(items) => {
+                Json::Array(items.iter().map(|item| item.to_json(case)).collect())
+            }
+            Value::Struct(fields) => fields.to_json(case),
+            Value::Enum { variant, fields } => {
+                let mut map = Map::new();
+                map.insert(case.variant(variant), fields.to_json(case));
+                Json::Object(map)
+            }
+        }
+    }
+}
+
+impl Fields {
+    /// Render as a JSON object; see [`Value::to_json`].
+    pub fn to_json(&self, case: KeyCase) -> Json {
+        let map = match self {
+            Fields::Unit => Map::new(),
+            Fields::Named(fields) => fields
+                .iter()
+                .map(|(name, value)| (case.field(name), value.to_json(case)))
+                .collect(),
+            Fields::Tuple(values) => values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (i.to_string(), value.to_json(case)))
+                .collect(),
+        };
+        Json::Object(map)
+    }
+}
+
+impl Decoded {
+    /// Render as `{ "name": ..., "data": ... }`, the shape of decoded
+    /// instructions and events in Anchor's TypeScript client.
+    pub fn to_json(&self, case: KeyCase) -> Json {
+        let mut map = Map::new();
+        map.insert("name".to_string(), Json::String(self.name.clone()));
+        map.insert("data".to_string(), self.value.to_json(case));
+        Json::Object(map)
+    }
+}
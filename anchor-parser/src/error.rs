@@ -0,0 +1,199 @@
+//! Program error decoding.
+//!
+//! Generated `errors::ErrorCode` enums cover the custom errors declared in a
+//! program's IDL. This module holds the program-agnostic pieces they build
+//! on: extracting custom error numbers from a [`TransactionError`] and
+//! parsing the `AnchorError ...` lines Anchor writes to the transaction logs.
+
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::TransactionError;
+
+/// Extract the failing top-level instruction index and custom error number
+/// from a transaction error.
+///
+/// Returns `None` unless the error is
+/// `TransactionError::InstructionError(_, InstructionError::Custom(_))`.
+///
+/// # Example
+///
+/// ```
+/// use anchor_parser::error::custom_error_code;
+/// use solana_sdk::instruction::InstructionError;
+/// use solana_sdk::transaction::TransactionError;
+///
+/// let err = TransactionError::InstructionError(2, InstructionError::Custom(6001));
+/// assert_eq!(custom_error_code(&err), Some((2, 6001)));
+/// ```
+pub fn custom_error_code(err: &TransactionError) -> Option<(u8, u32)> {
+    match err {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            Some((*index, *code))
+        }
+        _ => None,
+    }
+}
+
+/// Where an Anchor error was raised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorOrigin {
+    /// `AnchorError thrown in <file>:<line>` — raised by `err!` / `require!`.
+    Source { file: String, line: u32 },
+    /// `AnchorError caused by account: <name>` — raised by an account constraint.
+    AccountName(String),
+}
+
+/// Values compared by a failed `require_*!` check or account constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComparedValues {
+    /// Pubkeys logged after `Left:` / `Right:` lines.
+    Pubkeys(Pubkey, Pubkey),
+    /// Other values logged as `Left: <value>` / `Right: <value>`.
+    Values(String, String),
+}
+
+/// An error parsed from Anchor's `AnchorError ...` log lines.
+///
+/// Anchor logs one of the following before a program fails:
+///
+/// ```text
+/// Program log: AnchorError occurred. Error Code: <name>. Error Number: <n>. Error Message: <msg>.
+/// Program log: AnchorError thrown in <file>:<line>. Error Code: <name>. Error Number: <n>. Error Message: <msg>.
+/// Program log: AnchorError caused by account: <account>. Error Code: <name>. Error Number: <n>. Error Message: <msg>.
+/// ```
+///
+/// optionally followed by the `Left:` / `Right:` compared values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorErrorLog {
+    /// Program that failed, taken from the following `Program <id> failed`
+    /// line when present.
+    pub program_id: Option<Pubkey>,
+    /// Source location or account that raised the error.
+    pub origin: Option<ErrorOrigin>,
+    /// Error name (e.g. `ConstraintHasOne` or `SlippageExceeded`).
+    pub error_code: String,
+    /// Error number (e.g. `2001` or `6001`).
+    pub error_number: u32,
+    /// Error message.
+    pub error_msg: String,
+    /// Values compared by the failed check, if logged.
+    pub compared_values: Option<ComparedValues>,
+}
+
+impl AnchorErrorLog {
+    /// Find and parse the first Anchor error in a transaction's log messages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anchor_parser::error::{AnchorErrorLog, ErrorOrigin};
+    ///
+    /// let logs = [
+    ///     "Program log: AnchorError thrown in programs/amm/src/swap.rs:42. Error Code: SlippageExceeded. Error Number: 6001. Error Message: Slippage exceeded.",
+    /// ];
+    /// let err = AnchorErrorLog::from_logs(&logs).unwrap();
+    /// assert_eq!(err.error_number, 6001);
+    /// assert_eq!(
+    ///     err.origin,
+    ///     Some(ErrorOrigin::Source { file: "programs/amm/src/swap.rs".into(), line: 42 })
+    /// );
+    /// ```
+    pub fn from_logs<T, I>(logs: T) -> Option<Self>
+    where
+        T: IntoIterator<Item = I>,
+        I: AsRef<str>,
+    {
+        Self::all_from_logs(logs).into_iter().next()
+    }
+
+    /// Parse every Anchor error in a transaction's log messages, in order.
+    ///
+    /// A transaction can log more than one, e.g. when a program invoked
+    /// through CPI logs its error before the caller logs its own.
+    pub fn all_from_logs<T, I>(logs: T) -> Vec<Self>
+    where
+        T: IntoIterator<Item = I>,
+        I: AsRef<str>,
+    {
+        let logs: Vec<I> = logs.into_iter().collect();
+        let mut errors: Vec<(usize, Self)> = logs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, log)| Some((i, Self::parse_line(log.as_ref())?)))
+            .collect();
+
+        let starts: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        for (n, (i, error)) in errors.iter_mut().enumerate() {
+            // Context lines end where the next error starts
+            let end = starts.get(n + 1).copied().unwrap_or(logs.len());
+            let following = &logs[*i + 1..end];
+            let messages: Vec<&str> = following
+                .iter()
+                .map_while(|log| log.as_ref().strip_prefix("Program log: "))
+                .collect();
+            error.compared_values = parse_compared_values(&messages);
+            error.program_id = following.iter().find_map(|log| {
+                let (id, status) = log.as_ref().strip_prefix("Program ")?.split_once(' ')?;
+                if status.starts_with("failed") {
+                    id.parse().ok()
+                } else {
+                    None
+                }
+            });
+        }
+
+        errors.into_iter().map(|(_, error)| error).collect()
+    }
+
+    /// Parse a single `AnchorError ...` log line.
+    ///
+    /// Accepts the line with or without the `"Program log: "` prefix.
+    /// `program_id` and `compared_values` are always `None`, as they come
+    /// from the surrounding lines.
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let line = line.strip_prefix("Program log: ").unwrap_or(line);
+        let (head, rest) = line
+            .strip_prefix("AnchorError ")?
+            .split_once(". Error Code: ")?;
+        let (error_code, rest) = rest.split_once(". Error Number: ")?;
+        let (error_number, error_msg) = rest.split_once(". Error Message: ")?;
+
+        let origin = if head == "occurred" {
+            None
+        } else if let Some(location) = head.strip_prefix("thrown in ") {
+            let (file, line) = location.rsplit_once(':')?;
+            Some(ErrorOrigin::Source {
+                file: file.to_string(),
+                line: line.parse().ok()?,
+            })
+        } else if let Some(account) = head.strip_prefix("caused by account: ") {
+            Some(ErrorOrigin::AccountName(account.to_string()))
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            program_id: None,
+            origin,
+            error_code: error_code.to_string(),
+            error_number: error_number.parse().ok()?,
+            error_msg: error_msg.strip_suffix('.').unwrap_or(error_msg).to_string(),
+            compared_values: None,
+        })
+    }
+}
+
+/// Parse the `Left:` / `Right:` messages that follow an Anchor error.
+fn parse_compared_values(messages: &[&str]) -> Option<ComparedValues> {
+    match messages {
+        ["Left:", left, "Right:", right, ..] => Some(ComparedValues::Pubkeys(
+            left.parse().ok()?,
+            right.parse().ok()?,
+        )),
+        [left, right, ..] => Some(ComparedValues::Values(
+            left.strip_prefix("Left: ")?.to_string(),
+            right.strip_prefix("Right: ")?.to_string(),
+        )),
+        _ => None,
+    }
+}
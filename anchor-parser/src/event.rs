@@ -0,0 +1,176 @@
+//! Program event decoding.
+//!
+//! Generated `events` modules and the `utils::Event` enum decode the events
+//! declared in a program's IDL. This module holds the program-agnostic
+//! pieces they build on.
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Tag prefixing the instruction data of every `emit_cpi!` self-CPI.
+///
+/// Anchor's `EVENT_IX_TAG` (`0x1d9acb512ea545e4`) in little-endian order.
+pub const EVENT_IX_TAG: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+/// Seed of the PDA that signs `emit_cpi!` self-CPIs.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Address of a program's event authority, the PDA that signs its
+/// `emit_cpi!` self-CPIs.
+///
+/// Generated `events::event_authority()` functions cache this for their
+/// program.
+pub fn event_authority(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id).0
+}
+
+/// Strip [`EVENT_IX_TAG`] from `emit_cpi!` instruction data, returning the
+/// event discriminator and payload.
+///
+/// Returns `None` if the data does not start with the tag.
+///
+/// # Example
+///
+/// ```
+/// use anchor_parser::event::{EVENT_IX_TAG, strip_event_ix_tag};
+///
+/// let mut data = EVENT_IX_TAG.to_vec();
+/// data.extend_from_slice(&[1, 2, 3]);
+/// assert_eq!(strip_event_ix_tag(&data), Some(&[1, 2, 3][..]));
+/// assert_eq!(strip_event_ix_tag(&[0; 16]), None);
+/// ```
+pub fn strip_event_ix_tag(data: &[u8]) -> Option<&[u8]> {
+    data.strip_prefix(&EVENT_IX_TAG)
+}
+
+/// An event decoded from a `Program data:` log line, with where it was
+/// emitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoggedEvent<E> {
+    /// The decoded event.
+    pub event: E,
+    /// Invoke depth of the emitting program: `1` when emitted by a
+    /// top-level instruction, `2` or more when emitted during a CPI.
+    pub depth: usize,
+    /// Index of the top-level instruction that was executing.
+    pub instruction_index: usize,
+    /// Index of the `Program data:` line in the transaction logs.
+    pub log_index: usize,
+}
+
+impl<E> LoggedEvent<E> {
+    /// Attach the context of the log line an event was decoded from.
+    pub fn new(event: E, context: crate::logs::LogContext) -> Self {
+        Self {
+            event,
+            depth: context.depth,
+            instruction_index: context.instruction_index,
+            log_index: context.log_index,
+        }
+    }
+}
+
+/// An event log line or `emit_cpi!` entry that could not be decoded.
+///
+/// Returned by the generated `try_from_logs` / `try_from_cpi_logs`
+/// functions, which report failures instead of skipping them.
+#[derive(Debug)]
+pub enum EventDecodeError {
+    /// The discriminator matched an event but its payload failed to
+    /// deserialize, e.g. because a program upgrade changed the layout.
+    Deserialize {
+        /// Index of the entry in the input.
+        index: usize,
+        /// Event discriminator.
+        discriminator: Vec<u8>,
+        /// Borsh payload following the discriminator.
+        data: Vec<u8>,
+        /// The Borsh error.
+        error: std::io::Error,
+    },
+    /// The discriminator does not match any event in the IDL.
+    Unknown {
+        /// Index of the entry in the input.
+        index: usize,
+        /// Leading 8 bytes, or the whole entry if shorter.
+        discriminator: Vec<u8>,
+        /// Bytes following the discriminator.
+        data: Vec<u8>,
+    },
+}
+
+impl EventDecodeError {
+    /// Split `data` at `discriminator_len` into a [`Self::Deserialize`] error.
+    pub fn deserialize(
+        index: usize,
+        data: &[u8],
+        discriminator_len: usize,
+        error: std::io::Error,
+    ) -> Self {
+        let (discriminator, data) = data.split_at(discriminator_len.min(data.len()));
+        Self::Deserialize {
+            index,
+            discriminator: discriminator.to_vec(),
+            data: data.to_vec(),
+            error,
+        }
+    }
+
+    /// Split `data` after its leading 8 bytes into a [`Self::Unknown`] error.
+    pub fn unknown(index: usize, data: &[u8]) -> Self {
+        let (discriminator, data) = data.split_at(data.len().min(8));
+        Self::Unknown {
+            index,
+            discriminator: discriminator.to_vec(),
+            data: data.to_vec(),
+        }
+    }
+
+    /// Index of the entry in the input.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Deserialize { index, .. } | Self::Unknown { index, .. } => *index,
+        }
+    }
+
+    /// Discriminator of the entry.
+    pub fn discriminator(&self) -> &[u8] {
+        match self {
+            Self::Deserialize { discriminator, .. } | Self::Unknown { discriminator, .. } => {
+                discriminator
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for EventDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Deserialize {
+                index,
+                discriminator,
+                error,
+                ..
+            } => write!(
+                f,
+                "failed to decode event {discriminator:02x?} at index {index}: {error}"
+            ),
+            Self::Unknown {
+                index,
+                discriminator,
+                ..
+            } => write!(
+                f,
+                "unknown event discriminator {discriminator:02x?} at index {index}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EventDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialize { error, .. } => Some(error),
+            Self::Unknown { .. } => None,
+        }
+    }
+}
@@ -26,12 +26,14 @@
 //!
 //! | Module | Contents |
 //! |--------|----------|
-//! | `accounts` | Account structs with [`AccountDeserialize`] and `from_account_data` |
+//! | `accounts` | Account structs with [`AccountDeserialize`] / [`AccountSerialize`], `from_account_data` and `to_account_data` |
 //! | `events` | Event structs with `from_logs` and `from_cpi_logs` |
-//! | `instructions` | Builder functions returning [`solana_sdk::instruction::Instruction`] |
+//! | `instructions` | Builder functions returning [`solana_sdk::instruction::Instruction`] and an `Instruction` decoder enum |
 //! | `types` | Shared structs, enums, and type aliases from the IDL |
 //! | `constants` | Program constants with doc comments |
-//! | `utils` | `Event` / `Account` wrapper enums for generic parsing |
+//! | `errors` | `ErrorCode` enum of custom program errors |
+//! | `pda` | `find_<account>` functions deriving the PDAs declared in the IDL |
+//! | `utils` | `Event` / `Account` wrapper enums for generic parsing, `parse_transaction` |
 //!
 //! # Examples
 //!
@@ -45,6 +47,19 @@
 //!
 //! // Check discriminator
 //! assert_eq!(MyAccount::DISCRIMINATOR, [/* 8 bytes */]);
+//!
+//! // Write back, zero-padded to the original allocation size
+//! let data = account.to_account_data_padded(raw_bytes.len())?;
+//!
+//! // Field offsets, and with the `filters` feature, getProgramAccounts filters
+//! let offset = MyAccount::AUTHORITY_OFFSET;
+//! let filters = vec![MyAccount::discriminator_filter(), MyAccount::filter_authority(&key)];
+//!
+//! // Read a single field without deserializing the whole account
+//! let authority = MyAccount::read_authority(&raw_bytes)?;
+//!
+//! // Borrow a zero-copy account in place (payload must be aligned)
+//! let account = MyZeroCopyAccount::from_account_data_ref(&raw_bytes)?;
 //! ```
 //!
 //! ## Parsing events
@@ -57,9 +72,47 @@
 //! let events = SwapEvent::from_logs(&log_messages);
 //! let all_events = Event::from_logs(&log_messages);
 //!
+//! // Only events emitted by this program, with invoke depth and instruction index
+//! let all_events = Event::from_program_logs(&log_messages);
+//!
+//! // Lazily, reusing one decode buffer
+//! for event in Event::iter_logs(&log_messages) { /* ... */ }
+//!
 //! // Parse events from emit_cpi! inner instruction data (bs58-encoded)
 //! let events = SwapEvent::from_cpi_logs(&inner_ix_data_strings);
 //! let all_events = Event::from_cpi_logs(&inner_ix_data_strings);
+//!
+//! // Report lines that fail to decode instead of skipping them
+//! for result in Event::try_from_logs(&log_messages) {
+//!     let event = result?; // anchor_parser::event::EventDecodeError
+//! }
+//! ```
+//!
+//! ## Decoding program errors
+//!
+//! ```ignore
+//! use my_program::errors::ErrorCode;
+//!
+//! // InstructionError::Custom(6001)
+//! let err = ErrorCode::try_from(6001).unwrap();
+//! assert_eq!(err.name(), "SlippageExceeded");
+//! println!("{err}"); // error message from the IDL
+//!
+//! // From a failed transaction or its "AnchorError ..." log lines
+//! let err = ErrorCode::from_transaction_error(&tx_err, &tx.message);
+//! let err = ErrorCode::from_logs(&log_messages);
+//! ```
+//!
+//! See the [`error`] module for the program-agnostic parsers.
+//!
+//! ## Parsing transactions
+//!
+//! ```ignore
+//! use my_program::utils::parse_transaction;
+//!
+//! // Instructions targeting the program, with typed accounts and emit_cpi! events,
+//! // or an error for each one the IDL cannot decode
+//! let parsed = parse_transaction(&tx, &loaded_addresses, &inner_instructions);
 //! ```
 //!
 //! ## Building instructions
@@ -73,21 +126,64 @@
 //!     amount,
 //!     min_out,
 //! );
+//!
+//! // Or let the IDL fill in fixed addresses and PDAs
+//! let accounts = instructions::SwapAccountsPartial {
+//!     pool_state: Some(pool_address),
+//!     ..Default::default()
+//! }
+//! .with_pool_state_data(&pool_state) // accounts stored in the pool's fields
+//! .resolve(&args)?;
+//!
+//! // Decode instruction data and accounts back into typed values
+//! let decoded = instructions::Instruction::decode(&ix.data)?;
+//! let accounts = instructions::SwapAccounts::from_account_metas(&ix.accounts)?;
+//!
+//! // Decode the return value of an instruction with `returns`
+//! let quote = instructions::get_quote::decode_return_data(&return_data.program_id, &return_data.data)?;
+//! ```
+//!
+//! ## Serializing to JSON
+//!
+//! With the `serde` feature, generated types implement `Serialize` and
+//! `Deserialize`; see the [`serde`](crate::serde) module for the
+//! representation.
+//!
+//! ```ignore
+//! let json = serde_json::to_string(&my_event)?;
+//! ```
+//!
+//! ## Decoding with an IDL loaded at runtime
+//!
+//! ```ignore
+//! use anchor_parser::dynamic::Program;
+//!
+//! let program = Program::from_json(&idl_json)?;
+//! let account = program.decode_account(&raw_bytes)?;
+//! println!("{}: {:?}", account.name, account.value);
 //! ```
 
 /// Generates a module from an Anchor IDL JSON file.
 ///
 /// Looks for `idls/{name}.json` by walking up from `CARGO_MANIFEST_DIR`.
+/// Legacy (pre-0.30) IDLs are converted, with discriminators computed from
+/// names. Pass `program_id = "..."` to override the program address, or to
+/// supply one a legacy IDL lacks. Pass `only(accounts(A, B), events(C))` to
+/// generate just those items and the types they reference.
 ///
 /// # Generated items
 ///
 /// - `ID` — program [`Pubkey`](solana_sdk::pubkey::Pubkey)
-/// - `accounts` — account structs implementing [`AccountDeserialize`]
+/// - `accounts` — account structs implementing [`AccountDeserialize`] and
+///   [`AccountSerialize`]
 /// - `events` — event structs with `from_logs` / `from_cpi_logs`
 /// - `instructions` — builder functions returning [`Instruction`](solana_sdk::instruction::Instruction)
+///   and an `Instruction` enum decoding instruction data
 /// - `types` — shared structs, enums, and type aliases
 /// - `constants` — program constants
-/// - `utils` — `Event` / `Account` wrapper enums
+/// - `errors` — `ErrorCode` enum of custom program errors
+/// - `pda` — `find_<account>` PDA derivation functions
+/// - `utils` — `Event` / `Account` wrapper enums and `parse_transaction`
 ///
 /// # Example
 ///
@@ -98,6 +194,21 @@
 /// ```
 pub use anchor_parser_macros::declare_program;
 
+/// The Anchor IDL model, as read by [`declare_program!`] and
+/// [`dynamic::Program`].
+pub use anchor_parser_idl as idl;
+
+pub mod dynamic;
+pub mod error;
+pub mod event;
+pub mod logs;
+pub mod num;
+pub mod return_data;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+pub mod transaction;
+
 /// Trait implemented by all generated account types.
 ///
 /// Provides a discriminator constant and a method to deserialize from raw
@@ -126,13 +237,100 @@ pub trait AccountDeserialize: Sized {
     fn deserialize(data: &[u8]) -> Result<Self, std::io::Error>;
 }
 
+/// Trait implemented by all generated account types and `utils::Account`
+/// enums, writing them back to raw account data (discriminator prefix +
+/// serialized payload).
+///
+/// Useful to build test fixtures or account overrides for local simulation
+/// from modified on-chain state. Generated account types also expose
+/// `to_account_data` and `to_account_data_padded` methods.
+///
+/// # Example
+///
+/// ```ignore
+/// use anchor_parser::AccountSerialize;
+///
+/// fn write<T: AccountSerialize>(account: &T, original: &[u8]) -> std::io::Result<Vec<u8>> {
+///     // Keep the original allocation size, zero-filling the tail
+///     account.to_account_data_padded(original.len())
+/// }
+/// ```
+pub trait AccountSerialize {
+    /// Write the discriminator followed by the serialized payload.
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error>;
+
+    /// Serialize to raw account data, without padding.
+    fn to_account_data(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut data = Vec::new();
+        self.try_serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Serialize to raw account data zero-padded to `len` bytes, e.g. the
+    /// account's original allocation size.
+    ///
+    /// Returns an error if the serialized account is longer than `len`.
+    fn to_account_data_padded(&self, len: usize) -> Result<Vec<u8>, std::io::Error> {
+        let mut data = self.to_account_data()?;
+        if data.len() > len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Serialized account ({} bytes) exceeds the allocation of {len} bytes",
+                    data.len()
+                ),
+            ));
+        }
+        data.resize(len, 0);
+        Ok(data)
+    }
+}
+
+/// Trait implemented by all generated account types and `utils::Account`
+/// enums, giving access to their top-level `Pubkey` fields by name.
+///
+/// Used by the generated `*AccountsPartial::with_<account>_data` methods to
+/// fill accounts whose address is stored in another account's data, as
+/// declared by the IDL's `relations`, when the IDL doesn't give the type of
+/// that account.
+///
+/// # Example
+///
+/// ```ignore
+/// use anchor_parser::AccountFields;
+///
+/// let pool = my_program::accounts::Pool::from_account_data(&data)?;
+/// assert_eq!(pool.pubkey_field("token_a_vault"), Some(pool.token_a_vault));
+/// ```
+pub trait AccountFields {
+    /// Value of the `Pubkey` field `name`, or `None` if there is no such
+    /// field.
+    fn pubkey_field(&self, name: &str) -> Option<solana_sdk::pubkey::Pubkey>;
+}
+
 #[doc(hidden)]
 pub mod __private {
     pub use solana_sdk::instruction::{AccountMeta, Instruction};
+    pub use solana_sdk::message::VersionedMessage;
+    pub use solana_sdk::message::v0::LoadedAddresses;
     pub use solana_sdk::pubkey::Pubkey;
+    pub use solana_sdk::transaction::{TransactionError, VersionedTransaction};
 
-    pub use borsh::{BorshDeserialize, BorshSerialize};
-    pub use bytemuck::{Pod, Zeroable};
+    #[cfg(feature = "serde")]
+    pub use ::serde;
+    pub use borsh::{BorshDeserialize, BorshSerialize, object_length};
+    pub use bytemuck::{Pod, Zeroable, bytes_of};
+    #[cfg(feature = "filters")]
+    pub use solana_rpc_client_types::filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+
+    /// `memcmp` filter on field bytes. Base64-encoded, since RPC nodes reject
+    /// base58-encoded data longer than 128 bytes.
+    #[cfg(feature = "filters")]
+    pub fn memcmp_filter(offset: usize, bytes: &[u8]) -> RpcFilterType {
+        use base64::Engine;
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        RpcFilterType::Memcmp(Memcmp::new(offset, MemcmpEncodedBytes::Base64(encoded)))
+    }
 
     #[inline]
     pub fn base64_decode(input: &str) -> Option<Vec<u8>> {
@@ -145,8 +343,107 @@ pub mod __private {
         bs58::decode(input).into_vec().ok()
     }
 
+    #[cold]
+    pub fn missing_account(name: &str) -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Missing account `{name}`"),
+        )
+    }
+
     #[inline]
     pub fn bytemuck_read<T: bytemuck::Pod>(data: &[u8]) -> T {
         bytemuck::pod_read_unaligned(data)
     }
+
+    /// Range of the `T`-sized payload in zero-copy account data, checking
+    /// the discriminator and length.
+    pub fn bytemuck_payload<T: bytemuck::Pod>(
+        data: &[u8],
+        discriminator: &[u8],
+    ) -> Result<std::ops::Range<usize>, std::io::Error> {
+        let invalid =
+            |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+        let disc_len = discriminator.len();
+        if data.len() < disc_len {
+            return Err(invalid("Account data too short for discriminator"));
+        }
+        if data[..disc_len] != *discriminator {
+            return Err(invalid("Invalid account discriminator"));
+        }
+        let end = disc_len + std::mem::size_of::<T>();
+        if data.len() < end {
+            return Err(invalid("Account data too short for bytemuck type"));
+        }
+        Ok(disc_len..end)
+    }
+
+    /// Read the `T` at `offset` in account data without alignment
+    /// requirements.
+    ///
+    /// Panics if `data` is shorter than `offset + size_of::<T>()`.
+    ///
+    /// # Safety
+    ///
+    /// The bytes read must be a valid `T`.
+    #[inline]
+    pub unsafe fn read_unaligned_at<T: Copy>(data: &[u8], offset: usize) -> T {
+        let bytes = &data[offset..offset + std::mem::size_of::<T>()];
+        // SAFETY: `bytes` holds `size_of::<T>()` bytes, valid for `T` per
+        // the caller's guarantee
+        unsafe { std::ptr::read_unaligned(bytes.as_ptr().cast::<T>()) }
+    }
+
+    /// Deserialize the Borsh value at `offset` in account data, checking
+    /// the discriminator first.
+    pub fn borsh_read_field<T: BorshDeserialize>(
+        data: &[u8],
+        discriminator: &[u8],
+        offset: usize,
+    ) -> Result<T, std::io::Error> {
+        let invalid =
+            |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+        if data.len() < discriminator.len() {
+            return Err(invalid("Account data too short for discriminator"));
+        }
+        if data[..discriminator.len()] != *discriminator {
+            return Err(invalid("Invalid account discriminator"));
+        }
+        let mut reader = data
+            .get(offset..)
+            .ok_or_else(|| invalid("Account data too short for field"))?;
+        T::deserialize_reader(&mut reader)
+    }
+
+    #[cold]
+    fn misaligned<T>(payload: *const u8) -> std::io::Error {
+        let align = std::mem::align_of::<T>();
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Account payload is not aligned for zero-copy access: it requires \
+                 {align}-byte alignment but starts at offset {} modulo {align}; copy it \
+                 with `from_account_data` or use an aligned buffer",
+                payload as usize % align,
+            ),
+        )
+    }
+
+    pub fn bytemuck_ref<'a, T: bytemuck::Pod>(
+        data: &'a [u8],
+        discriminator: &[u8],
+    ) -> Result<&'a T, std::io::Error> {
+        let payload = &data[bytemuck_payload::<T>(data, discriminator)?];
+        bytemuck::try_from_bytes(payload).map_err(|_| misaligned::<T>(payload.as_ptr()))
+    }
+
+    pub fn bytemuck_mut<'a, T: bytemuck::Pod>(
+        data: &'a mut [u8],
+        discriminator: &[u8],
+    ) -> Result<&'a mut T, std::io::Error> {
+        let range = bytemuck_payload::<T>(data, discriminator)?;
+        let payload = &mut data[range];
+        let ptr = payload.as_ptr();
+        bytemuck::try_from_bytes_mut(payload).map_err(|_| misaligned::<T>(ptr))
+    }
 }
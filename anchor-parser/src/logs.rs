@@ -0,0 +1,219 @@
+//! Transaction log walking.
+//!
+//! The runtime brackets every program invocation with
+//! `Program <id> invoke [n]` and `Program <id> success` / `failed` lines.
+//! [`InvokeStack`] follows those lines to tell which program wrote each log
+//! line, so `Program data:` lines can be attributed to the program that
+//! emitted them rather than to whichever program's IDL happens to match.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use solana_sdk::pubkey::Pubkey;
+
+/// Where a log line was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogContext {
+    /// Program on top of the invoke stack.
+    pub program_id: Pubkey,
+    /// Invoke depth: `1` for a top-level instruction, `2` for a CPI it
+    /// made, and so on.
+    pub depth: usize,
+    /// Index of the top-level instruction being executed.
+    pub instruction_index: usize,
+    /// Index of the log line.
+    pub log_index: usize,
+}
+
+/// Tracks program invocations while walking a transaction's log messages.
+///
+/// # Example
+///
+/// ```
+/// use anchor_parser::logs::InvokeStack;
+///
+/// let logs = [
+///     "Program 11111111111111111111111111111111 invoke [1]",
+///     "Program 11111111111111111111111111111111 success",
+///     "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+///     "Program log: hello",
+/// ];
+///
+/// let mut stack = InvokeStack::new();
+/// for log in logs {
+///     stack.process(log);
+/// }
+/// let ctx = stack.context().unwrap();
+/// assert_eq!(ctx.program_id.to_string(), "ComputeBudget111111111111111111111111111111");
+/// assert_eq!((ctx.depth, ctx.instruction_index, ctx.log_index), (1, 1, 3));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InvokeStack {
+    stack: Vec<Pubkey>,
+    instruction_index: Option<usize>,
+    log_index: Option<usize>,
+}
+
+impl InvokeStack {
+    /// Create an empty stack, positioned before the first log line.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance past the next log line, updating the stack if it is an
+    /// `invoke`, `success` or `failed` line.
+    pub fn process(&mut self, log: &str) {
+        self.log_index = Some(self.log_index.map_or(0, |i| i + 1));
+
+        let Some((program_id, status)) = log
+            .strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(' '))
+        else {
+            return;
+        };
+
+        let invoke_depth = status
+            .strip_prefix("invoke [")
+            .and_then(|rest| rest.strip_suffix(']'));
+        let exited = status == "success" || status.starts_with("failed");
+        if invoke_depth.is_none() && !exited {
+            return;
+        }
+        let Ok(program_id) = program_id.parse::<Pubkey>() else {
+            return;
+        };
+
+        if let Some(depth) = invoke_depth {
+            if depth == "1" {
+                self.stack.clear();
+                self.instruction_index = Some(self.instruction_index.map_or(0, |i| i + 1));
+            }
+            self.stack.push(program_id);
+        } else if self.stack.last() == Some(&program_id) {
+            self.stack.pop();
+        }
+    }
+
+    /// Program on top of the stack, if any.
+    pub fn program_id(&self) -> Option<&Pubkey> {
+        self.stack.last()
+    }
+
+    /// Current invoke depth (`0` outside of any invocation).
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Context of the last processed log line, or `None` if it was written
+    /// outside of any invocation.
+    pub fn context(&self) -> Option<LogContext> {
+        Some(LogContext {
+            program_id: *self.stack.last()?,
+            depth: self.stack.len(),
+            instruction_index: self.instruction_index?,
+            log_index: self.log_index?,
+        })
+    }
+}
+
+/// Decode the `Program data: <base64>` lines written while `program_id` was
+/// on top of the invoke stack.
+///
+/// Lines written by other programs — including CPIs made by `program_id` —
+/// are skipped, as are lines that are not valid base64.
+pub fn program_data<T, I>(
+    program_id: Pubkey,
+    logs: T,
+) -> impl Iterator<Item = (LogContext, Vec<u8>)>
+where
+    T: IntoIterator<Item = I>,
+    I: AsRef<str>,
+{
+    let mut stack = InvokeStack::new();
+    logs.into_iter().filter_map(move |log| {
+        let log = log.as_ref();
+        stack.process(log);
+        let data = log.strip_prefix("Program data: ")?;
+        let context = stack.context().filter(|ctx| ctx.program_id == program_id)?;
+        Some((context, crate::__private::base64_decode(data)?))
+    })
+}
+
+/// Decodes `Program data: <base64>` lines into a reused buffer.
+///
+/// Only the first [`PREFIX_LEN`](Self::PREFIX_LEN) bytes are decoded before
+/// the caller decides whether the line is worth decoding in full, so lines
+/// carrying unwanted discriminators cost neither an allocation nor a full
+/// base64 decode.
+///
+/// # Example
+///
+/// ```
+/// use anchor_parser::logs::ProgramDataDecoder;
+///
+/// let mut decoder = ProgramDataDecoder::new();
+/// // base64 of [1, 2, 3, 4]
+/// let data = decoder.decode("Program data: AQIDBA==", |prefix| prefix.starts_with(&[1, 2]));
+/// assert_eq!(data, Some(&[1, 2, 3, 4][..]));
+/// assert_eq!(decoder.decode("Program data: AQIDBA==", |prefix| prefix.starts_with(&[9])), None);
+/// assert_eq!(decoder.decode("Program log: hello", |_| true), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProgramDataDecoder {
+    buf: Vec<u8>,
+}
+
+impl ProgramDataDecoder {
+    /// Number of leading bytes passed to the `accept` callback of
+    /// [`Self::decode`] (fewer if the data is shorter).
+    pub const PREFIX_LEN: usize = 12;
+
+    /// Create a decoder with an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether data whose leading bytes are `prefix`, as passed to the
+    /// `accept` callback of [`Self::decode`], may start with `discriminator`.
+    ///
+    /// Only the first [`Self::PREFIX_LEN`] bytes of longer discriminators
+    /// (Anchor allows custom discriminators of any length) can be checked
+    /// here; the caller must compare the rest on the decoded data.
+    ///
+    /// ```
+    /// use anchor_parser::logs::ProgramDataDecoder;
+    ///
+    /// let long = [7u8; 16];
+    /// assert!(ProgramDataDecoder::prefix_matches(&[7; 12], &long));
+    /// assert!(!ProgramDataDecoder::prefix_matches(&[7; 10], &long)); // data too short
+    /// assert!(ProgramDataDecoder::prefix_matches(&[1, 2, 3], &[1, 2]));
+    /// ```
+    pub fn prefix_matches(prefix: &[u8], discriminator: &[u8]) -> bool {
+        let len = discriminator.len().min(Self::PREFIX_LEN);
+        prefix.get(..len) == Some(&discriminator[..len])
+    }
+
+    /// Decode `log` if it is a `Program data:` line and `accept` returns
+    /// `true` for its leading bytes.
+    ///
+    /// Returns `None` for other lines, rejected prefixes and invalid base64.
+    /// The returned slice borrows the decoder's buffer, which the next call
+    /// overwrites.
+    pub fn decode(&mut self, log: &str, accept: impl FnOnce(&[u8]) -> bool) -> Option<&[u8]> {
+        let encoded = log.strip_prefix("Program data: ")?;
+
+        // Every 4 base64 characters decode to 3 bytes
+        const PREFIX_CHARS: usize = ProgramDataDecoder::PREFIX_LEN / 3 * 4;
+        let mut prefix = [0u8; Self::PREFIX_LEN];
+        let encoded = encoded.as_bytes();
+        let prefix_len = STANDARD
+            .decode_slice(&encoded[..encoded.len().min(PREFIX_CHARS)], &mut prefix)
+            .ok()?;
+        if !accept(&prefix[..prefix_len]) {
+            return None;
+        }
+
+        self.buf.clear();
+        STANDARD.decode_vec(encoded, &mut self.buf).ok()?;
+        Some(&self.buf)
+    }
+}
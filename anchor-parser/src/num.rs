@@ -0,0 +1,576 @@
+//! 256-bit integers for the IDL's `u256` and `i256` types.
+//!
+//! Generated code uses [`U256`] and [`I256`] for fields and arguments of
+//! these types. Both are stored as 32 little-endian bytes, matching their
+//! Borsh encoding and their layout in zero-copy accounts (alignment 1), and
+//! support comparisons, checked arithmetic, conversion from and to the
+//! primitive integers, and decimal parsing and formatting.
+//!
+//! # Example
+//!
+//! ```
+//! use anchor_parser::num::U256;
+//!
+//! let price: U256 = "340282366920938463463374607431768211456".parse().unwrap();
+//! assert_eq!(price, U256::from(u128::MAX).checked_add(U256::ONE).unwrap());
+//! assert_eq!(u128::try_from(price).ok(), None);
+//! assert_eq!(price.checked_div(U256::from(2u8)).unwrap().to_string(), "170141183460469231731687303715884105728");
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
+
+/// Unsigned 256-bit integer.
+#[derive(
+    Clone, Copy, Default, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize, Pod, Zeroable,
+)]
+#[repr(transparent)]
+pub struct U256([u8; 32]);
+
+/// Signed 256-bit integer, in two's complement.
+#[derive(
+    Clone, Copy, Default, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize, Pod, Zeroable,
+)]
+#[repr(transparent)]
+pub struct I256([u8; 32]);
+
+/// Error converting to an integer type that can't represent the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromIntError(());
+
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("out of range integral type conversion attempted")
+    }
+}
+
+impl std::error::Error for TryFromIntError {}
+
+/// Error parsing a [`U256`] or [`I256`] from a decimal string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseIntError {
+    /// The string is empty or contains a non-digit character.
+    InvalidDigit,
+    /// The value doesn't fit the target type.
+    Overflow,
+}
+
+impl fmt::Display for ParseIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseIntError::InvalidDigit => f.write_str("invalid digit found in string"),
+            ParseIntError::Overflow => f.write_str("number too large to fit in target type"),
+        }
+    }
+}
+
+impl std::error::Error for ParseIntError {}
+
+// ── Limbs ────────────────────────────────────────────────────────────
+
+/// 64-bit words, least significant first.
+type Limbs = [u64; 4];
+
+fn to_limbs(bytes: &[u8; 32]) -> Limbs {
+    std::array::from_fn(|i| u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap()))
+}
+
+fn from_limbs(limbs: Limbs) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    bytes
+}
+
+fn overflowing_add(a: Limbs, b: Limbs) -> (Limbs, bool) {
+    let mut out = [0; 4];
+    let mut carry = false;
+    for i in 0..4 {
+        let (sum, c1) = a[i].overflowing_add(b[i]);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        out[i] = sum;
+        carry = c1 || c2;
+    }
+    (out, carry)
+}
+
+fn overflowing_sub(a: Limbs, b: Limbs) -> (Limbs, bool) {
+    let mut out = [0; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (diff, b1) = a[i].overflowing_sub(b[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        out[i] = diff;
+        borrow = b1 || b2;
+    }
+    (out, borrow)
+}
+
+fn checked_mul(a: Limbs, b: Limbs) -> Option<Limbs> {
+    let mut out = [0u64; 4];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let product = u128::from(a[i]) * u128::from(b[j]) + carry;
+            if i + j < 4 {
+                let sum = u128::from(out[i + j]) + (product & u128::from(u64::MAX));
+                out[i + j] = sum as u64;
+                carry = (product >> 64) + (sum >> 64);
+            } else if product != 0 {
+                return None;
+            } else {
+                carry = 0;
+            }
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(out)
+}
+
+fn bit(a: &Limbs, i: usize) -> bool {
+    a[i / 64] >> (i % 64) & 1 == 1
+}
+
+fn shl1(a: Limbs) -> Limbs {
+    [
+        a[0] << 1,
+        a[1] << 1 | a[0] >> 63,
+        a[2] << 1 | a[1] >> 63,
+        a[3] << 1 | a[2] >> 63,
+    ]
+}
+
+fn cmp(a: &Limbs, b: &Limbs) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// Quotient and remainder; `b` must be non-zero.
+fn div_rem(a: Limbs, b: Limbs) -> (Limbs, Limbs) {
+    let mut quotient = [0; 4];
+    let mut remainder = [0; 4];
+    for i in (0..256).rev() {
+        remainder = shl1(remainder);
+        remainder[0] |= bit(&a, i) as u64;
+        if cmp(&remainder, &b) != Ordering::Less {
+            remainder = overflowing_sub(remainder, b).0;
+            quotient[i / 64] |= 1 << (i % 64);
+        }
+    }
+    (quotient, remainder)
+}
+
+/// Quotient and remainder of a division by a single word.
+fn div_rem_word(a: Limbs, b: u64) -> (Limbs, u64) {
+    let mut quotient = [0; 4];
+    let mut rem = 0u128;
+    for i in (0..4).rev() {
+        let cur = rem << 64 | u128::from(a[i]);
+        quotient[i] = (cur / u128::from(b)) as u64;
+        rem = cur % u128::from(b);
+    }
+    (quotient, rem as u64)
+}
+
+fn to_decimal(mut a: Limbs) -> String {
+    // Groups of 19 digits, least significant first
+    const BASE: u64 = 10_000_000_000_000_000_000;
+    let mut groups = Vec::new();
+    loop {
+        let (quotient, group) = div_rem_word(a, BASE);
+        groups.push(group);
+        a = quotient;
+        if a == [0; 4] {
+            break;
+        }
+    }
+    let mut out = groups.pop().unwrap_or(0).to_string();
+    for group in groups.iter().rev() {
+        out.push_str(&format!("{group:019}"));
+    }
+    out
+}
+
+fn parse_decimal(s: &str) -> Result<Limbs, ParseIntError> {
+    if s.is_empty() {
+        return Err(ParseIntError::InvalidDigit);
+    }
+    s.bytes().try_fold([0; 4], |acc, byte| {
+        let digit = match byte {
+            b'0'..=b'9' => u64::from(byte - b'0'),
+            _ => return Err(ParseIntError::InvalidDigit),
+        };
+        let acc = checked_mul(acc, [10, 0, 0, 0]).ok_or(ParseIntError::Overflow)?;
+        match overflowing_add(acc, [digit, 0, 0, 0]) {
+            (sum, false) => Ok(sum),
+            (_, true) => Err(ParseIntError::Overflow),
+        }
+    })
+}
+
+// ── U256 ─────────────────────────────────────────────────────────────
+
+impl U256 {
+    pub const ZERO: Self = Self([0; 32]);
+    pub const ONE: Self = Self::from_u128(1);
+    pub const MAX: Self = Self([0xff; 32]);
+
+    /// From 32 little-endian bytes, as stored on chain.
+    pub const fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// The 32 little-endian bytes, as stored on chain.
+    pub const fn to_le_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn from_be_bytes(mut bytes: [u8; 32]) -> Self {
+        bytes.reverse();
+        Self(bytes)
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let mut bytes = self.0;
+        bytes.reverse();
+        bytes
+    }
+
+    pub const fn from_u128(value: u128) -> Self {
+        let low = value.to_le_bytes();
+        let mut bytes = [0; 32];
+        let mut i = 0;
+        while i < 16 {
+            bytes[i] = low[i];
+            i += 1;
+        }
+        Self(bytes)
+    }
+
+    pub fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        match overflowing_add(to_limbs(&self.0), to_limbs(&rhs.0)) {
+            (sum, false) => Some(Self(from_limbs(sum))),
+            (_, true) => None,
+        }
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match overflowing_sub(to_limbs(&self.0), to_limbs(&rhs.0)) {
+            (diff, false) => Some(Self(from_limbs(diff))),
+            (_, true) => None,
+        }
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        checked_mul(to_limbs(&self.0), to_limbs(&rhs.0)).map(|product| Self(from_limbs(product)))
+    }
+
+    /// `None` if `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_div_rem(rhs).map(|(quotient, _)| quotient)
+    }
+
+    /// `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        self.checked_div_rem(rhs).map(|(_, remainder)| remainder)
+    }
+
+    fn checked_div_rem(self, rhs: Self) -> Option<(Self, Self)> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem(to_limbs(&self.0), to_limbs(&rhs.0));
+        Some((Self(from_limbs(quotient)), Self(from_limbs(remainder))))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp(&to_limbs(&self.0), &to_limbs(&other.0))
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_u256_from {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for U256 {
+            fn from(value: $ty) -> Self {
+                Self::from_u128(value.into())
+            }
+        }
+    )*};
+}
+
+impl_u256_from!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_u256_try_from_signed {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<$ty> for U256 {
+            type Error = TryFromIntError;
+
+            fn try_from(value: $ty) -> Result<Self, TryFromIntError> {
+                u128::try_from(value)
+                    .map(Self::from_u128)
+                    .map_err(|_| TryFromIntError(()))
+            }
+        }
+    )*};
+}
+
+impl_u256_try_from_signed!(i8, i16, i32, i64, i128);
+
+macro_rules! impl_try_from_u256 {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<U256> for $ty {
+            type Error = TryFromIntError;
+
+            fn try_from(value: U256) -> Result<Self, TryFromIntError> {
+                let (low, high) = value.0.split_at(16);
+                if high.iter().any(|&b| b != 0) {
+                    return Err(TryFromIntError(()));
+                }
+                let low = u128::from_le_bytes(low.try_into().unwrap());
+                <$ty>::try_from(low).map_err(|_| TryFromIntError(()))
+            }
+        }
+    )*};
+}
+
+impl_try_from_u256!(u8, u16, u32, u64, u128);
+
+impl FromStr for U256 {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, ParseIntError> {
+        let digits = s.strip_prefix('+').unwrap_or(s);
+        parse_decimal(digits).map(|limbs| Self(from_limbs(limbs)))
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "", &to_decimal(to_limbs(&self.0)))
+    }
+}
+
+impl fmt::Debug for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// ── I256 ─────────────────────────────────────────────────────────────
+
+impl I256 {
+    pub const ZERO: Self = Self([0; 32]);
+    pub const ONE: Self = Self::from_i128(1);
+    pub const MIN: Self = {
+        let mut bytes = [0; 32];
+        bytes[31] = 0x80;
+        Self(bytes)
+    };
+    pub const MAX: Self = {
+        let mut bytes = [0xff; 32];
+        bytes[31] = 0x7f;
+        Self(bytes)
+    };
+
+    /// From 32 little-endian two's complement bytes, as stored on chain.
+    pub const fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// The 32 little-endian two's complement bytes, as stored on chain.
+    pub const fn to_le_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn from_be_bytes(mut bytes: [u8; 32]) -> Self {
+        bytes.reverse();
+        Self(bytes)
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let mut bytes = self.0;
+        bytes.reverse();
+        bytes
+    }
+
+    pub const fn from_i128(value: i128) -> Self {
+        let low = value.to_le_bytes();
+        let fill = if value < 0 { 0xff } else { 0 };
+        let mut bytes = [fill; 32];
+        let mut i = 0;
+        while i < 16 {
+            bytes[i] = low[i];
+            i += 1;
+        }
+        Self(bytes)
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0[31] & 0x80 != 0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+
+    /// Absolute value, which always fits a [`U256`].
+    pub fn unsigned_abs(self) -> U256 {
+        let limbs = to_limbs(&self.0);
+        if self.is_negative() {
+            U256(from_limbs(overflowing_sub([0; 4], limbs).0))
+        } else {
+            U256(self.0)
+        }
+    }
+
+    /// `None` for [`I256::MIN`].
+    pub fn checked_neg(self) -> Option<Self> {
+        Self::from_sign_magnitude(!self.is_negative(), self.unsigned_abs())
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let sum = Self(from_limbs(
+            overflowing_add(to_limbs(&self.0), to_limbs(&rhs.0)).0,
+        ));
+        // Overflow iff both operands share a sign the result doesn't
+        let overflow =
+            self.is_negative() == rhs.is_negative() && sum.is_negative() != self.is_negative();
+        (!overflow).then_some(sum)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let diff = Self(from_limbs(
+            overflowing_sub(to_limbs(&self.0), to_limbs(&rhs.0)).0,
+        ));
+        let overflow =
+            self.is_negative() != rhs.is_negative() && diff.is_negative() != self.is_negative();
+        (!overflow).then_some(diff)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let magnitude = self.unsigned_abs().checked_mul(rhs.unsigned_abs())?;
+        Self::from_sign_magnitude(self.is_negative() != rhs.is_negative(), magnitude)
+    }
+
+    /// Rounds toward zero. `None` if `rhs` is zero, or for
+    /// `I256::MIN / -1`.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        let quotient = self.unsigned_abs().checked_div(rhs.unsigned_abs())?;
+        Self::from_sign_magnitude(self.is_negative() != rhs.is_negative(), quotient)
+    }
+
+    /// Takes the sign of `self`. `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        let remainder = self.unsigned_abs().checked_rem(rhs.unsigned_abs())?;
+        Self::from_sign_magnitude(self.is_negative(), remainder)
+    }
+
+    fn from_sign_magnitude(negative: bool, magnitude: U256) -> Option<Self> {
+        let limbs = to_limbs(&magnitude.0);
+        let value = if negative {
+            Self(from_limbs(overflowing_sub([0; 4], limbs).0))
+        } else {
+            Self(magnitude.0)
+        };
+        // Zero is the only value whose sign may differ from the request
+        (value.is_negative() == negative || magnitude.is_zero()).then_some(value)
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .is_negative()
+            .cmp(&self.is_negative())
+            .then_with(|| cmp(&to_limbs(&self.0), &to_limbs(&other.0)))
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_i256_from {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for I256 {
+            fn from(value: $ty) -> Self {
+                Self::from_i128(value.into())
+            }
+        }
+    )*};
+}
+
+impl_i256_from!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<u128> for I256 {
+    fn from(value: u128) -> Self {
+        I256(U256::from_u128(value).0)
+    }
+}
+
+macro_rules! impl_try_from_i256 {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<I256> for $ty {
+            type Error = TryFromIntError;
+
+            fn try_from(value: I256) -> Result<Self, TryFromIntError> {
+                let (low, high) = value.0.split_at(16);
+                let low = i128::from_le_bytes(low.try_into().unwrap());
+                // Fits an i128 iff the high half only extends its sign
+                let fill = if low < 0 { 0xff } else { 0 };
+                if high.iter().any(|&b| b != fill) {
+                    return Err(TryFromIntError(()));
+                }
+                <$ty>::try_from(low).map_err(|_| TryFromIntError(()))
+            }
+        }
+    )*};
+}
+
+impl_try_from_i256!(i8, i16, i32, i64, i128);
+
+impl FromStr for I256 {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, ParseIntError> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let magnitude = U256(from_limbs(parse_decimal(digits)?));
+        Self::from_sign_magnitude(negative, magnitude).ok_or(ParseIntError::Overflow)
+    }
+}
+
+impl fmt::Display for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = to_decimal(to_limbs(&self.unsigned_abs().0));
+        f.pad_integral(!self.is_negative(), "", &digits)
+    }
+}
+
+impl fmt::Debug for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,80 @@
+//! Instruction return data decoding.
+//!
+//! Generated `instructions::<name>` modules decode the return values of
+//! instructions that declare `returns` in the IDL. This module holds the
+//! program-agnostic pieces they build on: parsing the
+//! `Program return: <program_id> <base64>` log line and decoding return data
+//! whose trailing zero bytes were trimmed.
+
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+
+/// Maximum size of the return data an instruction can set, as enforced by
+/// the runtime.
+pub const MAX_RETURN_DATA: usize = 1024;
+
+/// Parse a `Program return: <program_id> <base64>` log line.
+///
+/// # Example
+///
+/// ```
+/// use anchor_parser::return_data::parse_return_log;
+///
+/// let (program_id, data) =
+///     parse_return_log("Program return: 11111111111111111111111111111111 AQID").unwrap();
+/// assert_eq!(program_id, solana_sdk::pubkey::Pubkey::default());
+/// assert_eq!(data, [1, 2, 3]);
+/// ```
+pub fn parse_return_log(log: &str) -> Option<(Pubkey, Vec<u8>)> {
+    let (program_id, data) = log.strip_prefix("Program return: ")?.split_once(' ')?;
+    Some((
+        program_id.parse().ok()?,
+        crate::__private::base64_decode(data)?,
+    ))
+}
+
+/// Return data set by `program_id`, taken from the last
+/// `Program return:` line it wrote.
+///
+/// The runtime only keeps the return data set last, so for a transaction
+/// invoking `program_id` several times this is the value of the last
+/// invocation that returned data.
+pub fn from_logs<T, I>(program_id: &Pubkey, logs: T) -> Option<Vec<u8>>
+where
+    T: IntoIterator<Item = I>,
+    I: AsRef<str>,
+{
+    logs.into_iter()
+        .filter_map(|log| parse_return_log(log.as_ref()))
+        .filter(|(id, _)| id == program_id)
+        .last()
+        .map(|(_, data)| data)
+}
+
+/// Borsh-deserialize a return value.
+///
+/// The runtime trims trailing zero bytes from the return data reported by
+/// simulations and transaction metadata, so the input is read as if padded
+/// with zeros up to [`MAX_RETURN_DATA`]. Reading past that, e.g. because of a
+/// corrupt length prefix, is an error.
+///
+/// # Example
+///
+/// ```
+/// use anchor_parser::return_data::decode;
+///
+/// // 256u64 with its trailing zero bytes trimmed
+/// assert_eq!(decode::<u64>(&[0, 1]).unwrap(), 256);
+/// assert_eq!(decode::<u64>(&[]).unwrap(), 0);
+/// ```
+pub fn decode<T: BorshDeserialize>(data: &[u8]) -> Result<T, std::io::Error> {
+    let mut padded = [0u8; MAX_RETURN_DATA];
+    let mut reader = match padded.get_mut(..data.len()) {
+        Some(prefix) => {
+            prefix.copy_from_slice(data);
+            &padded[..]
+        }
+        None => data,
+    };
+    T::deserialize(&mut reader)
+}
@@ -0,0 +1,223 @@
+//! Serde support for generated types, enabled by the `serde` feature.
+//!
+//! With the feature on, generated types, accounts, events and instruction
+//! argument structs derive `Serialize` and `Deserialize`, following the
+//! conventions of Anchor's TypeScript client:
+//!
+//! - pubkeys as base58 strings
+//! - `u64`, `i64`, `u128`, `i128`, [`U256`] and [`I256`] as decimal strings,
+//!   so JSON consumers don't lose precision
+//! - fixed-size arrays of any length
+//!
+//! Field names and enum variants keep their Rust spelling, and enums use
+//! serde's default external tagging.
+//!
+//! Fields whose types need these conventions are serialized through
+//! [`SerdeField`] via `#[serde(with = "anchor_parser::serde::field")]`;
+//! every generated struct and enum implements it by delegating to its
+//! derived impls.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let event: my_program::events::SwapEvent = /* ... */;
+//! let json = serde_json::to_string(&event)?;
+//! // {"pool":"5BUw…","amount_in":"1000000","a_to_b":true}
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeTuple, Serializer};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::num::{I256, U256};
+
+/// Serde representation of a generated field type.
+///
+/// Implemented for the primitive types that can appear in an IDL, for
+/// `Option`, `Vec` and arrays of those, and for every generated struct and
+/// enum.
+pub trait SerdeField: Sized {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// `#[serde(with = "...")]` adapter routing a field through [`SerdeField`].
+pub mod field {
+    use super::SerdeField;
+    use ::serde::{Deserializer, Serializer};
+
+    pub fn serialize<T: SerdeField, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: SerdeField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Borrowed value serialized through [`SerdeField`].
+struct Ser<'a, T>(&'a T);
+
+impl<T: SerdeField> Serialize for Ser<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Owned value deserialized through [`SerdeField`].
+struct De<T>(T);
+
+impl<'de, T: SerdeField> Deserialize<'de> for De<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(De)
+    }
+}
+
+// ── Primitives ───────────────────────────────────────────────────────
+
+macro_rules! impl_native {
+    ($($ty:ty),*) => {$(
+        impl SerdeField for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                Serialize::serialize(self, serializer)
+            }
+
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <$ty as Deserialize>::deserialize(deserializer)
+            }
+        }
+    )*};
+}
+
+impl_native!(bool, u8, i8, u16, i16, u32, i32, f32, f64, String);
+
+/// Accepts a decimal string, or a JSON number that fits the target type.
+struct IntVisitor<T>(PhantomData<T>);
+
+macro_rules! impl_string_int {
+    ($($ty:ty),*) => {$(
+        impl<'de> Visitor<'de> for IntVisitor<$ty> {
+            type Value = $ty;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(concat!("a decimal string or number fitting ", stringify!($ty)))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<$ty, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<$ty, E> {
+                <$ty>::try_from(v).map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<$ty, E> {
+                <$ty>::try_from(v).map_err(E::custom)
+            }
+
+            fn visit_u128<E: de::Error>(self, v: u128) -> Result<$ty, E> {
+                <$ty>::try_from(v).map_err(E::custom)
+            }
+
+            fn visit_i128<E: de::Error>(self, v: i128) -> Result<$ty, E> {
+                <$ty>::try_from(v).map_err(E::custom)
+            }
+        }
+
+        impl SerdeField for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(IntVisitor::<$ty>(PhantomData))
+            }
+        }
+    )*};
+}
+
+impl_string_int!(u64, i64, u128, i128, U256, I256);
+
+impl SerdeField for Pubkey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+// ── Containers ───────────────────────────────────────────────────────
+
+impl<T: SerdeField> SerdeField for Option<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(value) => serializer.serialize_some(&Ser(value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Option::<De<T>>::deserialize(deserializer)?.map(|De(value)| value))
+    }
+}
+
+impl<T: SerdeField> SerdeField for Vec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Ser))
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<De<T>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|De(value)| value).collect())
+    }
+}
+
+impl<T: SerdeField, const N: usize> SerdeField for [T; N] {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for value in self {
+            tuple.serialize_element(&Ser(value))?;
+        }
+        tuple.end()
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+        impl<'de, T: SerdeField, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+            type Value = [T; N];
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an array of length {N}")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[T; N], A::Error> {
+                let mut values = Vec::with_capacity(N);
+                while let Some(De(value)) = seq.next_element()? {
+                    if values.len() == N {
+                        return Err(de::Error::invalid_length(N + 1, &self));
+                    }
+                    values.push(value);
+                }
+                let len = values.len();
+                values
+                    .try_into()
+                    .map_err(|_| de::Error::invalid_length(len, &self))
+            }
+        }
+
+        deserializer.deserialize_tuple(N, ArrayVisitor::<T, N>(PhantomData))
+    }
+}
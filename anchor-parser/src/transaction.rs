@@ -0,0 +1,138 @@
+//! Transaction walking.
+//!
+//! Helpers for visiting every instruction of a transaction — top-level and
+//! CPI — with program IDs resolved against the full account key list. The
+//! generated `utils::parse_transaction` builds on these to decode the
+//! instructions targeting one program.
+
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::message::inner_instruction::InnerInstruction;
+use solana_sdk::message::v0::LoadedAddresses;
+use solana_sdk::pubkey::Pubkey;
+
+/// Inner instructions invoked while executing one top-level instruction.
+///
+/// Mirrors an entry of the transaction status meta's `innerInstructions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InnerInstructions {
+    /// Index of the top-level instruction that invoked these instructions.
+    pub index: u8,
+    /// Invoked instructions, in execution order.
+    pub instructions: Vec<InnerInstruction>,
+}
+
+/// One instruction of a transaction, top-level or CPI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionInstruction<'a> {
+    /// Index of the top-level instruction (or of the one that invoked this
+    /// CPI).
+    pub index: u8,
+    /// Position within the top-level instruction's inner instructions, or
+    /// `None` for the top-level instruction itself.
+    pub inner_index: Option<usize>,
+    /// Invocation stack height: `1` for top-level instructions, `0` when
+    /// unknown (inner instructions recorded before stack heights existed).
+    pub stack_height: u8,
+    /// Program the instruction targets.
+    pub program_id: Pubkey,
+    /// Indices into the transaction's account keys.
+    pub accounts: &'a [u8],
+    /// Instruction data.
+    pub data: &'a [u8],
+}
+
+/// An instruction targeting the program that could not be decoded, e.g. one
+/// added to the program after its IDL was published.
+#[derive(Debug)]
+pub struct InstructionDecodeError {
+    /// Index of the top-level instruction (or of the one that invoked this
+    /// CPI).
+    pub index: u8,
+    /// Position within the top-level instruction's inner instructions, or
+    /// `None` for a top-level instruction.
+    pub inner_index: Option<usize>,
+    /// Invocation stack height (`1` for top-level, `0` if unknown).
+    pub stack_height: u8,
+    /// Raw instruction data.
+    pub data: Vec<u8>,
+    /// The decoding error.
+    pub error: std::io::Error,
+}
+
+impl InstructionDecodeError {
+    /// Record the failure to decode `ix`.
+    pub fn new(ix: &TransactionInstruction<'_>, error: std::io::Error) -> Self {
+        Self {
+            index: ix.index,
+            inner_index: ix.inner_index,
+            stack_height: ix.stack_height,
+            data: ix.data.to_vec(),
+            error,
+        }
+    }
+}
+
+impl std::fmt::Display for InstructionDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to decode instruction {}", self.index)?;
+        if let Some(inner_index) = self.inner_index {
+            write!(f, " (inner {inner_index})")?;
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl std::error::Error for InstructionDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// All account keys of a message: static keys followed by the writable and
+/// readonly addresses loaded from lookup tables.
+pub fn account_keys(message: &VersionedMessage, loaded_addresses: &LoadedAddresses) -> Vec<Pubkey> {
+    message
+        .static_account_keys()
+        .iter()
+        .chain(&loaded_addresses.writable)
+        .chain(&loaded_addresses.readonly)
+        .copied()
+        .collect()
+}
+
+/// Visit every instruction of a transaction in execution order.
+///
+/// Each top-level instruction is followed by the inner instructions it
+/// invoked. Instructions whose program ID index is out of range for
+/// `account_keys` are skipped.
+pub fn instructions<'a>(
+    message: &'a VersionedMessage,
+    account_keys: &'a [Pubkey],
+    inner_instructions: &'a [InnerInstructions],
+) -> impl Iterator<Item = TransactionInstruction<'a>> + 'a {
+    message
+        .instructions()
+        .iter()
+        .enumerate()
+        .flat_map(move |(index, ix)| {
+            let index = index as u8;
+            let top_level = std::iter::once((None, 1, ix));
+            let inner = inner_instructions
+                .iter()
+                .filter(move |inner| inner.index == index)
+                .flat_map(|inner| inner.instructions.iter().enumerate())
+                .map(|(i, inner)| (Some(i), inner.stack_height, &inner.instruction));
+            top_level
+                .chain(inner)
+                .filter_map(move |(inner_index, stack_height, ix)| {
+                    Some(TransactionInstruction {
+                        index,
+                        inner_index,
+                        stack_height,
+                        program_id: *account_keys.get(ix.program_id_index as usize)?,
+                        accounts: &ix.accounts,
+                        data: &ix.data,
+                    })
+                })
+        })
+}
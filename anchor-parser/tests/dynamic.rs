@@ -0,0 +1,491 @@
+use anchor_parser::declare_program;
+
+declare_program!(pumpfun);
+declare_program!(meteora_dlmm);
+declare_program!(legacy_escrow);
+
+#[cfg(test)]
+mod tests {
+    use super::{legacy_escrow, meteora_dlmm, pumpfun};
+    use anchor_parser::dynamic::{DecodeError, Fields, Program, Value};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn pumpfun_program() -> Program {
+        Program::from_json(include_str!("../../idls/pumpfun.json")).unwrap()
+    }
+
+    fn dlmm_program() -> Program {
+        Program::from_json(include_str!("../../idls/meteora_dlmm.json")).unwrap()
+    }
+
+    // ── Loading ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_load_idls() {
+        assert_eq!(pumpfun_program().program_id(), Some(pumpfun::ID));
+        assert_eq!(dlmm_program().program_id(), Some(meteora_dlmm::ID));
+
+        let legacy = Program::from_json(include_str!("../../idls/legacy_escrow.json")).unwrap();
+        assert_eq!(legacy.program_id(), Some(legacy_escrow::ID));
+        assert_eq!(legacy.idl().metadata.spec, "legacy");
+        assert_eq!(
+            legacy.idl().accounts[0].discriminator,
+            legacy_escrow::accounts::EscrowAccount::DISCRIMINATOR
+        );
+    }
+
+    // ── Borsh accounts ──────────────────────────────────────────────
+
+    #[test]
+    fn test_decode_borsh_account() {
+        use borsh::BorshSerialize;
+        use pumpfun::accounts::BondingCurve;
+
+        let bc = BondingCurve {
+            virtual_token_reserves: 1_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: true,
+            creator: Pubkey::new_unique(),
+            is_mayhem_mode: false,
+        };
+        let mut data = BondingCurve::DISCRIMINATOR.to_vec();
+        bc.serialize(&mut data).unwrap();
+        // Spare allocated space is ignored
+        data.extend_from_slice(&[0; 16]);
+
+        let decoded = pumpfun_program().decode_account(&data).unwrap();
+        assert_eq!(decoded.name, "BondingCurve");
+        let value = &decoded.value;
+        assert_eq!(
+            value.get("virtual_sol_reserves"),
+            Some(&Value::U64(30_000_000_000))
+        );
+        assert_eq!(value.get("complete").and_then(Value::as_bool), Some(true));
+        assert_eq!(
+            value.get("creator").and_then(Value::as_pubkey),
+            Some(&bc.creator)
+        );
+    }
+
+    #[test]
+    fn test_decode_enum_and_option() {
+        use borsh::BorshSerialize;
+        use legacy_escrow::accounts::EscrowAccount;
+        use legacy_escrow::types::EscrowState;
+
+        let program = Program::from_json(include_str!("../../idls/legacy_escrow.json")).unwrap();
+        let taker = Pubkey::new_unique();
+        let escrow = EscrowAccount {
+            initializer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: 5,
+            taker_amount: 7,
+            expires_at: Some(-1),
+            state: EscrowState::Settled { taker },
+            bump: 255,
+        };
+        let mut data = EscrowAccount::DISCRIMINATOR.to_vec();
+        escrow.serialize(&mut data).unwrap();
+
+        let value = program.decode_account(&data).unwrap().value;
+        assert_eq!(
+            value.get("expires_at"),
+            Some(&Value::Option(Some(Box::new(Value::I64(-1)))))
+        );
+        assert_eq!(
+            value.get("state"),
+            Some(&Value::Enum {
+                variant: "Settled".to_string(),
+                fields: Fields::Named(vec![("taker".to_string(), Value::Pubkey(taker))]),
+            })
+        );
+
+        let mut data = EscrowAccount::DISCRIMINATOR.to_vec();
+        EscrowAccount {
+            state: EscrowState::Cancelled(3),
+            expires_at: None,
+            ..escrow
+        }
+        .serialize(&mut data)
+        .unwrap();
+        let value = program.decode_account(&data).unwrap().value;
+        assert_eq!(value.get("expires_at"), Some(&Value::Option(None)));
+        assert_eq!(
+            value.get("state"),
+            Some(&Value::Enum {
+                variant: "Cancelled".to_string(),
+                fields: Fields::Tuple(vec![Value::I64(3)]),
+            })
+        );
+    }
+
+    // ── Bytemuck accounts ───────────────────────────────────────────
+
+    #[test]
+    fn test_decode_bytemuck_account() {
+        use anchor_parser::__private::Zeroable;
+        use meteora_dlmm::accounts::LbPair;
+
+        let mut pair = LbPair::zeroed();
+        pair.active_id = -4_200;
+        pair.bin_step = 25;
+        pair.token_x_mint = Pubkey::new_unique();
+        pair.reward_infos[1].reward_rate = u128::MAX - 1;
+        pair.reward_infos[1].last_update_time = 1_700_000_000;
+        pair.bin_array_bitmap[15] = u64::MAX;
+        pair.version = 2;
+
+        let mut data = LbPair::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&pair));
+
+        let decoded = dlmm_program().decode_account(&data).unwrap();
+        assert_eq!(decoded.name, "LbPair");
+        let value = &decoded.value;
+        assert_eq!(value.get("active_id"), Some(&Value::I32(-4_200)));
+        assert_eq!(value.get("bin_step"), Some(&Value::U16(25)));
+        assert_eq!(
+            value.get("token_x_mint"),
+            Some(&Value::Pubkey(pair.token_x_mint))
+        );
+        let Some(Value::Array(rewards)) = value.get("reward_infos") else {
+            panic!("expected reward_infos array");
+        };
+        assert_eq!(
+            rewards[1].get("reward_rate"),
+            Some(&Value::U128(u128::MAX - 1))
+        );
+        assert_eq!(
+            rewards[1].get("last_update_time"),
+            Some(&Value::U64(1_700_000_000))
+        );
+        let Some(Value::Array(bitmap)) = value.get("bin_array_bitmap") else {
+            panic!("expected bin_array_bitmap array");
+        };
+        assert_eq!(bitmap[15], Value::U64(u64::MAX));
+        assert_eq!(value.get("version"), Some(&Value::U8(2)));
+    }
+
+    #[test]
+    fn test_decode_bytemuck_nested_arrays() {
+        use anchor_parser::__private::Zeroable;
+        use meteora_dlmm::accounts::BinArray;
+
+        let mut bin_array = BinArray::zeroed();
+        bin_array.index = -3;
+        bin_array.lb_pair = Pubkey::new_unique();
+        bin_array.bins[69].price = 1 << 64;
+        bin_array.bins[69].amount_y = 12;
+
+        let mut data = BinArray::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&bin_array));
+
+        let value = dlmm_program().decode_account(&data).unwrap().value;
+        assert_eq!(value.get("index"), Some(&Value::I64(-3)));
+        let Some(Value::Array(bins)) = value.get("bins") else {
+            panic!("expected bins array");
+        };
+        assert_eq!(bins.len(), 70);
+        assert_eq!(bins[69].get("price"), Some(&Value::U128(1 << 64)));
+        assert_eq!(bins[69].get("amount_y").and_then(Value::as_u64), Some(12));
+    }
+
+    #[test]
+    fn test_decode_bytemuck_account_too_short() {
+        use meteora_dlmm::accounts::BinArray;
+
+        let mut data = BinArray::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0; 100]);
+        assert!(matches!(
+            dlmm_program().decode_account(&data),
+            Err(DecodeError::InvalidData(_))
+        ));
+    }
+
+    // ── Instructions ────────────────────────────────────────────────
+
+    #[test]
+    fn test_decode_instruction() {
+        use pumpfun::instructions::BuyArgs;
+        use pumpfun::types::OptionBool;
+
+        let mut data = BuyArgs::DISCRIMINATOR.to_vec();
+        borsh::to_writer(
+            &mut data,
+            &BuyArgs {
+                amount: 1_000,
+                max_sol_cost: 2_000,
+                track_volume: OptionBool(true),
+            },
+        )
+        .unwrap();
+
+        let decoded = pumpfun_program().decode_instruction(&data).unwrap();
+        assert_eq!(decoded.name, "buy");
+        assert_eq!(decoded.value.get("amount"), Some(&Value::U64(1_000)));
+        assert_eq!(decoded.value.get("max_sol_cost"), Some(&Value::U64(2_000)));
+        assert_eq!(
+            decoded.value.get("track_volume"),
+            Some(&Value::Struct(Fields::Tuple(vec![Value::Bool(true)])))
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_discriminator() {
+        let program = pumpfun_program();
+        assert_eq!(
+            program.decode_instruction(&[0; 16]),
+            Err(DecodeError::UnknownDiscriminator)
+        );
+        assert_eq!(
+            program.decode_account(&[1, 2]),
+            Err(DecodeError::UnknownDiscriminator)
+        );
+    }
+
+    // ── Events ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_events_from_logs() {
+        use base64::Engine;
+        use borsh::BorshSerialize;
+        use pumpfun::events::CompleteEvent;
+
+        let evt = CompleteEvent {
+            user: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            bonding_curve: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+        };
+        let mut data = CompleteEvent::DISCRIMINATOR.to_vec();
+        evt.serialize(&mut data).unwrap();
+        let log = format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(&data)
+        );
+
+        let program = pumpfun_program();
+        let events = program.events_from_logs(["Program log: Instruction: Buy", log.as_str()]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "CompleteEvent");
+        assert_eq!(
+            events[0].value.get("mint").and_then(Value::as_pubkey),
+            Some(&evt.mint)
+        );
+        assert_eq!(
+            events[0].value.get("timestamp").and_then(Value::as_i64),
+            Some(1_700_000_000)
+        );
+
+        // Truncated payload
+        assert!(matches!(
+            program.decode_event(&data[..data.len() - 1]),
+            Err(DecodeError::InvalidData(_))
+        ));
+    }
+
+    // ── Generics ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_decode_generic_types() {
+        let program = Program::from_json(
+            r#"{
+                "address": "11111111111111111111111111111111",
+                "metadata": { "name": "generic", "version": "0.1.0", "spec": "0.1.0" },
+                "instructions": [],
+                "accounts": [{ "name": "Holder", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8] }],
+                "types": [
+                    {
+                        "name": "Holder",
+                        "type": {
+                            "kind": "struct",
+                            "fields": [{
+                                "name": "inner",
+                                "type": {
+                                    "defined": {
+                                        "name": "Wrapper",
+                                        "generics": [
+                                            { "kind": "type", "type": "u16" },
+                                            { "kind": "const", "value": "2" }
+                                        ]
+                                    }
+                                }
+                            }]
+                        }
+                    },
+                    {
+                        "name": "Wrapper",
+                        "generics": [
+                            { "kind": "type", "name": "T" },
+                            { "kind": "const", "name": "N", "type": "usize" }
+                        ],
+                        "type": {
+                            "kind": "struct",
+                            "fields": [
+                                { "name": "value", "type": { "generic": "T" } },
+                                { "name": "items", "type": { "array": [{ "generic": "T" }, { "generic": "N" }] } }
+                            ]
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 1, 0, 2, 0, 3, 0];
+        let value = program.decode_account(&data).unwrap().value;
+        let inner = value.get("inner").unwrap();
+        assert_eq!(inner.get("value"), Some(&Value::U16(1)));
+        assert_eq!(
+            inner.get("items"),
+            Some(&Value::Array(vec![Value::U16(2), Value::U16(3)]))
+        );
+    }
+
+    #[test]
+    fn test_decode_vec_of_zero_sized_elements() {
+        let program = Program::from_json(
+            r#"{
+                "address": "11111111111111111111111111111111",
+                "metadata": { "name": "zst", "version": "0.1.0", "spec": "0.1.0" },
+                "instructions": [],
+                "accounts": [{ "name": "Holder", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8] }],
+                "types": [
+                    {
+                        "name": "Holder",
+                        "type": {
+                            "kind": "struct",
+                            "fields": [{ "name": "items", "type": { "vec": { "defined": { "name": "Empty" } } } }]
+                        }
+                    },
+                    { "name": "Empty", "type": { "kind": "struct", "fields": [] } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        // A length prefix of u32::MAX is rejected without looping over it
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 0xff, 0xff, 0xff, 0xff];
+        let err = program.decode_account(&data).unwrap_err();
+        assert!(matches!(err, DecodeError::InvalidData(_)), "{err}");
+
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0];
+        let value = program.decode_account(&data).unwrap().value;
+        assert_eq!(value.get("items"), Some(&Value::Vec(Vec::new())));
+    }
+
+    // ── JSON ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_account_to_json() {
+        use anchor_parser::dynamic::KeyCase;
+        use borsh::BorshSerialize;
+        use legacy_escrow::accounts::EscrowAccount;
+        use legacy_escrow::types::EscrowState;
+        use serde_json::json;
+
+        let program = Program::from_json(include_str!("../../idls/legacy_escrow.json")).unwrap();
+        let escrow = EscrowAccount {
+            initializer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: u64::MAX,
+            taker_amount: 7,
+            expires_at: Some(-1),
+            state: EscrowState::Settled {
+                taker: Pubkey::default(),
+            },
+            bump: 255,
+        };
+        let mut data = EscrowAccount::DISCRIMINATOR.to_vec();
+        escrow.serialize(&mut data).unwrap();
+        let value = program.decode_account(&data).unwrap().value;
+
+        assert_eq!(
+            value.to_json(KeyCase::Camel),
+            json!({
+                "initializer": escrow.initializer.to_string(),
+                "mint": escrow.mint.to_string(),
+                "amount": "18446744073709551615",
+                "takerAmount": "7",
+                "expiresAt": "-1",
+                "state": { "settled": { "taker": "11111111111111111111111111111111" } },
+                "bump": 255,
+            })
+        );
+        assert_eq!(value.to_json(KeyCase::Snake)["taker_amount"], json!("7"));
+
+        let mut data = EscrowAccount::DISCRIMINATOR.to_vec();
+        EscrowAccount {
+            expires_at: None,
+            state: EscrowState::Open,
+            ..escrow
+        }
+        .serialize(&mut data)
+        .unwrap();
+        let json = program
+            .decode_account(&data)
+            .unwrap()
+            .value
+            .to_json(KeyCase::Camel);
+        assert_eq!(json["expiresAt"], json!(null));
+        assert_eq!(json["state"], json!({ "open": {} }));
+    }
+
+    #[test]
+    fn test_instruction_to_json() {
+        use anchor_parser::dynamic::KeyCase;
+        use pumpfun::instructions::BuyArgs;
+        use pumpfun::types::OptionBool;
+        use serde_json::json;
+
+        let mut data = BuyArgs::DISCRIMINATOR.to_vec();
+        borsh::to_writer(
+            &mut data,
+            &BuyArgs {
+                amount: 1_000,
+                max_sol_cost: 2_000,
+                track_volume: OptionBool(false),
+            },
+        )
+        .unwrap();
+
+        let decoded = pumpfun_program().decode_instruction(&data).unwrap();
+        assert_eq!(
+            decoded.to_json(KeyCase::Camel),
+            json!({
+                "name": "buy",
+                "data": {
+                    "amount": "1000",
+                    "maxSolCost": "2000",
+                    "trackVolume": { "0": false },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_wide_integers_to_json() {
+        use anchor_parser::dynamic::KeyCase;
+        use anchor_parser::num::{I256, U256};
+        use serde_json::json;
+
+        assert_eq!(
+            Value::U256(U256::MAX).to_json(KeyCase::Camel),
+            json!("115792089237316195423570985008687907853269984665640564039457584007913129639935")
+        );
+        assert_eq!(
+            Value::I256(I256::MIN).to_json(KeyCase::Camel),
+            json!("-57896044618658097711785492504343953926634992332820282019728792003956564819968")
+        );
+        assert_eq!(
+            Value::I128(i128::MIN).to_json(KeyCase::Camel),
+            json!("-170141183460469231731687303715884105728")
+        );
+        assert_eq!(
+            Value::U32(u32::MAX).to_json(KeyCase::Camel),
+            json!(4294967295u32)
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use anchor_parser::declare_program;
+
+// Legacy (pre-0.30) IDL: no discriminators, camelCase names, `isMut` /
+// `isSigner` accounts and the address under `metadata.address`.
+declare_program!(legacy_escrow);
+
+// Same IDL under another module name, pointed at another deployment.
+mod devnet {
+    anchor_parser::declare_program!(
+        escrow,
+        path = "idls/legacy_escrow.json",
+        program_id = "11111111111111111111111111111111",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::legacy_escrow;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_program_id_from_metadata() {
+        assert_eq!(
+            legacy_escrow::ID.to_string(),
+            "Eyq72ejQSQGqRyuwWBJQsMmHCepkNDNZ6wmJXsPnZ4TS"
+        );
+    }
+
+    #[test]
+    fn test_program_id_argument() {
+        assert_eq!(super::devnet::escrow::ID, Pubkey::default());
+    }
+
+    #[test]
+    fn test_path_argument() {
+        use super::devnet::escrow;
+
+        assert_eq!(
+            escrow::accounts::EscrowAccount::DISCRIMINATOR,
+            legacy_escrow::accounts::EscrowAccount::DISCRIMINATOR
+        );
+        let ix = escrow::instructions::cancel(
+            &escrow::ID,
+            &escrow::instructions::CancelAccounts {
+                initializer: Pubkey::new_unique(),
+                escrow_account: Pubkey::new_unique(),
+            },
+        );
+        assert_eq!(ix.program_id, Pubkey::default());
+    }
+
+    // ── Discriminators ──────────────────────────────────────────────
+
+    #[test]
+    fn test_account_discriminator() {
+        use legacy_escrow::accounts::EscrowAccount;
+        // sha256("account:EscrowAccount")[..8]
+        assert_eq!(
+            EscrowAccount::DISCRIMINATOR,
+            [36, 69, 48, 18, 128, 225, 125, 135]
+        );
+    }
+
+    #[test]
+    fn test_instruction_discriminators() {
+        use legacy_escrow::instructions::{CancelArgs, ExchangeArgs, InitializeEscrowArgs};
+        // sha256("global:<snake_case name>")[..8]
+        assert_eq!(
+            InitializeEscrowArgs::DISCRIMINATOR,
+            [243, 160, 77, 153, 11, 92, 48, 209]
+        );
+        assert_eq!(
+            ExchangeArgs::DISCRIMINATOR,
+            [47, 3, 27, 97, 215, 236, 219, 144]
+        );
+        assert_eq!(
+            CancelArgs::DISCRIMINATOR,
+            [232, 219, 223, 41, 219, 236, 220, 190]
+        );
+    }
+
+    #[test]
+    fn test_event_discriminators() {
+        use legacy_escrow::events::{EscrowCreated, EscrowSettled};
+        // sha256("event:<Name>")[..8]
+        assert_eq!(
+            EscrowCreated::DISCRIMINATOR,
+            [70, 127, 105, 102, 92, 97, 7, 173]
+        );
+        assert_eq!(
+            EscrowSettled::DISCRIMINATOR,
+            [97, 27, 150, 55, 203, 179, 173, 23]
+        );
+    }
+
+    // ── Accounts & types ────────────────────────────────────────────
+
+    #[test]
+    fn test_escrow_account_from_account_data() {
+        use borsh::BorshSerialize;
+        use legacy_escrow::accounts::EscrowAccount;
+        use legacy_escrow::types::EscrowState;
+
+        let taker = Pubkey::new_unique();
+        let escrow = EscrowAccount {
+            initializer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: 1_000,
+            taker_amount: 2_000,
+            expires_at: Some(1_700_000_000),
+            state: EscrowState::Settled { taker },
+            bump: 254,
+        };
+
+        let mut data = EscrowAccount::DISCRIMINATOR.to_vec();
+        escrow.serialize(&mut data).unwrap();
+
+        let parsed = EscrowAccount::from_account_data(&data).unwrap();
+        assert_eq!(parsed.initializer, escrow.initializer);
+        assert_eq!(parsed.taker_amount, 2_000);
+        assert_eq!(parsed.expires_at, Some(1_700_000_000));
+        assert!(matches!(parsed.state, EscrowState::Settled { taker: t } if t == taker));
+        assert_eq!(parsed.bump, 254);
+    }
+
+    #[test]
+    fn test_enum_tuple_and_unit_variants() {
+        use borsh::BorshSerialize;
+        use legacy_escrow::types::EscrowState;
+
+        let mut data = Vec::new();
+        EscrowState::Cancelled(-5).serialize(&mut data).unwrap();
+        assert_eq!(data, [2, 251, 255, 255, 255, 255, 255, 255, 255]);
+
+        let mut data = Vec::new();
+        EscrowState::Open.serialize(&mut data).unwrap();
+        assert_eq!(data, [0]);
+    }
+
+    #[test]
+    fn test_init_space_counts_largest_option_and_variant() {
+        use legacy_escrow::accounts::EscrowAccount;
+        use legacy_escrow::types::EscrowState;
+
+        // 2 pubkeys, 2 u64, Option<i64>, EscrowState (tag + pubkey), u8
+        assert_eq!(EscrowAccount::INIT_SPACE, 32 + 32 + 8 + 8 + 9 + 33 + 1);
+
+        let mut escrow = EscrowAccount {
+            initializer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: 1,
+            taker_amount: 2,
+            expires_at: Some(3),
+            state: EscrowState::Settled {
+                taker: Pubkey::new_unique(),
+            },
+            bump: 255,
+        };
+        assert_eq!(
+            escrow.serialized_len().unwrap(),
+            8 + EscrowAccount::INIT_SPACE
+        );
+
+        escrow.expires_at = None;
+        escrow.state = EscrowState::Open;
+        assert_eq!(
+            escrow.serialized_len().unwrap(),
+            8 + EscrowAccount::INIT_SPACE - 8 - 32
+        );
+    }
+
+    // ── Instructions ────────────────────────────────────────────────
+
+    #[test]
+    fn test_initialize_escrow_instruction() {
+        use borsh::BorshSerialize;
+        use legacy_escrow::instructions;
+        use legacy_escrow::types::InitializeParams;
+
+        let accounts = instructions::InitializeEscrowAccounts {
+            initializer: Pubkey::from([1; 32]),
+            escrow_account: Pubkey::from([2; 32]),
+            token_accounts_mint: Pubkey::from([3; 32]),
+            token_accounts_initializer_deposit_token_account: Pubkey::from([4; 32]),
+            token_accounts_vault: Pubkey::from([5; 32]),
+            system_program: Pubkey::from([6; 32]),
+            token_program: Pubkey::from([7; 32]),
+        };
+        let params = InitializeParams {
+            amount: 1_000,
+            taker_amount: 2_000,
+            expires_at: None,
+            memo: "gm".to_string(),
+            allow_list: vec![Pubkey::from([8; 32])],
+        };
+
+        let ix = instructions::initialize_escrow(&legacy_escrow::ID, &accounts, params.clone());
+
+        let mut expected_data = instructions::InitializeEscrowArgs::DISCRIMINATOR.to_vec();
+        params.serialize(&mut expected_data).unwrap();
+        assert_eq!(ix.data, expected_data);
+
+        let flags: Vec<_> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_writable, meta.is_signer))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                (Pubkey::from([1; 32]), true, true),
+                (Pubkey::from([2; 32]), true, false),
+                (Pubkey::from([3; 32]), false, false),
+                (Pubkey::from([4; 32]), true, false),
+                (Pubkey::from([5; 32]), true, false),
+                (Pubkey::from([6; 32]), false, false),
+                (Pubkey::from([7; 32]), false, false),
+            ]
+        );
+
+        match instructions::Instruction::decode(&ix.data).unwrap() {
+            instructions::Instruction::InitializeEscrow(args) => {
+                assert_eq!(args.params.memo, "gm");
+                assert_eq!(args.params.allow_list, params.allow_list);
+            }
+            other => panic!("unexpected instruction: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_exchange_instruction_array_arg() {
+        use legacy_escrow::instructions;
+
+        let ix = instructions::exchange(
+            &legacy_escrow::ID,
+            &instructions::ExchangeAccounts {
+                taker: Pubkey::from([1; 32]),
+                initializer: Pubkey::from([2; 32]),
+                escrow_account: Pubkey::from([3; 32]),
+                mint: Pubkey::from([4; 32]),
+                referrer: None,
+            },
+            [10, 20],
+        );
+
+        let mut expected_data = instructions::ExchangeArgs::DISCRIMINATOR.to_vec();
+        expected_data.extend_from_slice(&10u64.to_le_bytes());
+        expected_data.extend_from_slice(&20u64.to_le_bytes());
+        assert_eq!(ix.data, expected_data);
+        assert!(ix.accounts[0].is_signer);
+    }
+
+    #[test]
+    fn test_relations_fill_accounts() {
+        use legacy_escrow::accounts::EscrowAccount;
+        use legacy_escrow::instructions::ExchangeAccountsPartial;
+        use legacy_escrow::types::EscrowState;
+
+        let escrow = EscrowAccount {
+            initializer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: 1,
+            taker_amount: 1,
+            expires_at: None,
+            state: EscrowState::Open,
+            bump: 255,
+        };
+
+        let accounts = ExchangeAccountsPartial {
+            taker: Some(Pubkey::new_unique()),
+            escrow_account: Some(Pubkey::new_unique()),
+            ..Default::default()
+        }
+        .with_escrow_account_data(&escrow)
+        .resolve()
+        .unwrap();
+        assert_eq!(accounts.initializer, escrow.initializer);
+        assert_eq!(accounts.mint, escrow.mint);
+    }
+
+    #[test]
+    fn test_cancel_return_data() {
+        use legacy_escrow::instructions::cancel;
+
+        assert_eq!(cancel::decode_return(&42u64.to_le_bytes()).unwrap(), 42);
+    }
+
+    // ── Events ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_event_from_logs() {
+        use base64::Engine;
+        use borsh::BorshSerialize;
+        use legacy_escrow::events::EscrowSettled;
+        use legacy_escrow::utils::Event;
+
+        let evt = EscrowSettled {
+            escrow: Pubkey::new_unique(),
+            taker_amount: 2_000,
+        };
+        let mut data = EscrowSettled::DISCRIMINATOR.to_vec();
+        evt.serialize(&mut data).unwrap();
+        let log = format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(&data)
+        );
+
+        let events = Event::from_logs([log.as_str()]);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            Event::EscrowSettled(e) if e.escrow == evt.escrow && e.taker_amount == 2_000
+        ));
+    }
+
+    // ── Serde ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_serde_account() {
+        use legacy_escrow::accounts::EscrowAccount;
+        use legacy_escrow::types::EscrowState;
+        use serde_json::json;
+
+        let escrow = EscrowAccount {
+            initializer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: u64::MAX,
+            taker_amount: 2_000,
+            expires_at: Some(-1),
+            state: EscrowState::Cancelled(-5),
+            bump: 254,
+        };
+
+        let value = serde_json::to_value(escrow).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "initializer": escrow.initializer.to_string(),
+                "mint": escrow.mint.to_string(),
+                "amount": "18446744073709551615",
+                "taker_amount": "2000",
+                "expires_at": "-1",
+                "state": { "Cancelled": "-5" },
+                "bump": 254,
+            })
+        );
+
+        let parsed: EscrowAccount = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.initializer, escrow.initializer);
+        assert_eq!(parsed.amount, u64::MAX);
+        assert_eq!(parsed.expires_at, Some(-1));
+        assert!(matches!(parsed.state, EscrowState::Cancelled(-5)));
+    }
+
+    #[test]
+    fn test_serde_accepts_numbers_for_large_integers() {
+        use legacy_escrow::events::EscrowSettled;
+        use serde_json::json;
+
+        let escrow = Pubkey::new_unique();
+        let evt: EscrowSettled = serde_json::from_value(json!({
+            "escrow": escrow.to_string(),
+            "taker_amount": 2_000,
+        }))
+        .unwrap();
+        assert_eq!(evt.escrow, escrow);
+        assert_eq!(evt.taker_amount, 2_000);
+
+        let err = serde_json::from_value::<EscrowSettled>(json!({
+            "escrow": "not a pubkey",
+            "taker_amount": "1",
+        }));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_serde_instruction_args() {
+        use legacy_escrow::instructions::ExchangeArgs;
+        use serde_json::json;
+
+        let args = ExchangeArgs {
+            expected_amounts: [1, u64::MAX],
+        };
+        let value = serde_json::to_value(&args).unwrap();
+        assert_eq!(
+            value,
+            json!({ "expected_amounts": ["1", "18446744073709551615"] })
+        );
+
+        let parsed: ExchangeArgs = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.expected_amounts, args.expected_amounts);
+
+        let short = serde_json::from_value::<ExchangeArgs>(json!({ "expected_amounts": ["1"] }));
+        assert!(short.is_err());
+    }
+
+    // ── Errors & constants ──────────────────────────────────────────
+
+    #[test]
+    fn test_errors() {
+        use legacy_escrow::errors::ErrorCode;
+
+        assert_eq!(ErrorCode::InvalidAmount.code(), 6000);
+        assert_eq!(ErrorCode::Expired.msg(), "Escrow has expired");
+    }
+
+    #[test]
+    fn test_constants() {
+        use legacy_escrow::constants;
+
+        assert_eq!(constants::ESCROW_SEED, b"escrow");
+        assert_eq!(constants::MAX_DURATION, 86_400);
+    }
+}
@@ -260,7 +260,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = EvtClosePosition::from_logs(&[log_line.as_str()]);
+        let events = EvtClosePosition::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].pool, evt.pool);
     }
@@ -286,7 +286,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = EvtClaimPositionFee::from_logs(&[log_line.as_str()]);
+        let events = EvtClaimPositionFee::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].fee_a_claimed, 1_000_000);
         assert_eq!(events[0].fee_b_claimed, 2_000_000);
@@ -310,7 +310,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = EvtSetPoolStatus::from_logs(&[log_line.as_str()]);
+        let events = EvtSetPoolStatus::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].status, 2);
     }
@@ -334,7 +334,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = EvtClaimPartnerFee::from_logs(&[log_line.as_str()]);
+        let events = EvtClaimPartnerFee::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].token_a_amount, 5_000_000);
         assert_eq!(events[0].token_b_amount, 10_000_000);
@@ -370,7 +370,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = Event::from_logs(&[log_line.as_str()]);
+        let events = Event::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         match &events[0] {
             Event::EvtCreatePosition(e) => {
@@ -605,7 +605,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1143,7 +1143,7 @@ mod tests {
                 program,
             },
             index,
-            config_parameters.clone(),
+            config_parameters,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1200,7 +1200,7 @@ mod tests {
                 program,
             },
             index,
-            config_parameters.clone(),
+            config_parameters,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1428,7 +1428,7 @@ mod tests {
                 pod_aligned_fee_rate_limiter,
                 pod_aligned_fee_market_cap_scheduler,
             },
-            _ixs.clone(),
+            _ixs,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1590,7 +1590,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1685,7 +1685,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1792,7 +1792,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1923,7 +1923,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2169,7 +2169,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2281,7 +2281,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2415,7 +2415,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            _params.clone(),
+            _params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2492,7 +2492,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            _params.clone(),
+            _params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2541,7 +2541,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2760,6 +2760,550 @@ mod tests {
         assert_ix(&gen_ix, &expected_ix);
     }
 
+    // ── Account relations ───────────────────────────────────────────
+
+    fn sample_pool() -> meteora_damm_v2::accounts::Pool {
+        use meteora_damm_v2::accounts::Pool;
+
+        let mut data = Pool::DISCRIMINATOR.to_vec();
+        data.resize(8 + std::mem::size_of::<Pool>(), 0);
+        let mut pool = Pool::from_account_data(&data).unwrap();
+        pool.token_a_mint = Pubkey::new_unique();
+        pool.token_b_mint = Pubkey::new_unique();
+        pool.token_a_vault = Pubkey::new_unique();
+        pool.token_b_vault = Pubkey::new_unique();
+        pool
+    }
+
+    #[test]
+    fn test_accounts_partial_with_relation_data() {
+        use anchor_parser::AccountFields;
+        use meteora_damm_v2::instructions::SwapAccountsPartial;
+
+        let pool = sample_pool();
+        assert_eq!(pool.pubkey_field("token_a_vault"), Some(pool.token_a_vault));
+        assert_eq!(pool.pubkey_field("liquidity"), None);
+
+        let token_program: Pubkey = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+            .parse()
+            .unwrap();
+        let pool_key = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let accounts = SwapAccountsPartial {
+            pool: Some(pool_key),
+            input_token_account: Some(Pubkey::new_unique()),
+            output_token_account: Some(Pubkey::new_unique()),
+            token_a_mint: Some(pool.token_a_mint),
+            token_b_mint: Some(pool.token_b_mint),
+            payer: Some(payer),
+            token_a_program: Some(token_program),
+            token_b_program: Some(token_program),
+            ..Default::default()
+        }
+        .with_pool_data(&pool)
+        .resolve()
+        .unwrap();
+
+        assert_eq!(accounts.pool, pool_key);
+        assert_eq!(accounts.token_a_vault, pool.token_a_vault);
+        assert_eq!(accounts.token_b_vault, pool.token_b_vault);
+        assert_eq!(
+            accounts.event_authority,
+            meteora_damm_v2::events::event_authority()
+        );
+        assert_eq!(accounts.program, meteora_damm_v2::ID);
+        assert_eq!(accounts.referral_token_account, None);
+
+        // Without the pool data the vaults are missing
+        let err = SwapAccountsPartial {
+            pool: Some(pool_key),
+            ..Default::default()
+        }
+        .resolve()
+        .unwrap_err();
+        assert!(err.to_string().contains("input_token_account"));
+    }
+
+    #[test]
+    fn test_with_relation_data_keeps_set_accounts() {
+        use meteora_damm_v2::instructions::SwapAccountsPartial;
+        use meteora_damm_v2::utils::Account;
+
+        let pool = sample_pool();
+        let vault = Pubkey::new_unique();
+        // The `utils::Account` enum works as well
+        let partial = SwapAccountsPartial {
+            token_a_vault: Some(vault),
+            ..Default::default()
+        }
+        .with_pool_data(&Account::Pool(pool));
+        assert_eq!(partial.token_a_vault, Some(vault));
+        assert_eq!(partial.token_b_vault, Some(pool.token_b_vault));
+        // `token_a_mint` is not listed in the swap instruction's relations
+        assert_eq!(partial.token_a_mint, None);
+    }
+
+    // ── Errors ──────────────────────────────────────────────────────
+
+    fn message_with_program(program_id: Pubkey) -> solana_sdk::message::VersionedMessage {
+        let payer = Pubkey::new_unique();
+        let ixs = [
+            solana_sdk::instruction::Instruction::new_with_bytes(
+                solana_sdk::pubkey!("ComputeBudget111111111111111111111111111111"),
+                &[2, 0, 0, 0, 0],
+                vec![],
+            ),
+            solana_sdk::instruction::Instruction::new_with_bytes(
+                program_id,
+                &[0; 8],
+                vec![solana_sdk::instruction::AccountMeta::new(payer, true)],
+            ),
+        ];
+        solana_sdk::message::VersionedMessage::Legacy(solana_sdk::message::Message::new(
+            &ixs,
+            Some(&payer),
+        ))
+    }
+
+    #[test]
+    fn test_error_from_transaction_error() {
+        use meteora_damm_v2::errors::ErrorCode;
+        use solana_sdk::instruction::InstructionError;
+        use solana_sdk::transaction::TransactionError;
+
+        let message = message_with_program(meteora_damm_v2::ID);
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(6002));
+        assert_eq!(
+            ErrorCode::from_transaction_error(&err, &message),
+            Some(ErrorCode::ExceededSlippage)
+        );
+
+        // Wrong instruction index (compute budget program)
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(6002));
+        assert_eq!(ErrorCode::from_transaction_error(&err, &message), None);
+
+        // Not a custom error
+        let err = TransactionError::InstructionError(1, InstructionError::InvalidAccountData);
+        assert_eq!(ErrorCode::from_transaction_error(&err, &message), None);
+
+        // Another program's instruction
+        let message = message_with_program(Pubkey::new_unique());
+        let err = TransactionError::InstructionError(1, InstructionError::Custom(6002));
+        assert_eq!(ErrorCode::from_transaction_error(&err, &message), None);
+
+        // Malformed message with an out-of-range program id index
+        let mut message = message_with_program(meteora_damm_v2::ID);
+        if let solana_sdk::message::VersionedMessage::Legacy(legacy) = &mut message {
+            legacy.instructions[1].program_id_index = 100;
+        }
+        assert_eq!(ErrorCode::from_transaction_error(&err, &message), None);
+    }
+
+    #[test]
+    fn test_error_from_logs() {
+        use anchor_parser::error::{AnchorErrorLog, ErrorOrigin};
+        use meteora_damm_v2::errors::ErrorCode;
+
+        let logs = [
+            "Program cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG invoke [1]",
+            "Program log: Instruction: Swap",
+            "Program log: AnchorError thrown in programs/cp-amm/src/instructions/ix_swap.rs:183. Error Code: ExceededSlippage. Error Number: 6002. Error Message: Exceeded slippage tolerance.",
+            "Program cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG consumed 41234 of 200000 compute units",
+            "Program cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG failed: custom program error: 0x1772",
+        ];
+
+        let log = AnchorErrorLog::from_logs(logs).unwrap();
+        assert_eq!(log.program_id, Some(meteora_damm_v2::ID));
+        assert_eq!(
+            log.origin,
+            Some(ErrorOrigin::Source {
+                file: "programs/cp-amm/src/instructions/ix_swap.rs".to_string(),
+                line: 183,
+            })
+        );
+        assert_eq!(log.error_code, "ExceededSlippage");
+        assert_eq!(log.error_number, 6002);
+        assert_eq!(log.error_msg, "Exceeded slippage tolerance");
+        assert_eq!(log.compared_values, None);
+
+        assert_eq!(
+            ErrorCode::from_logs(logs),
+            Some(ErrorCode::ExceededSlippage)
+        );
+    }
+
+    #[test]
+    fn test_error_from_logs_account_constraint() {
+        use anchor_parser::error::{AnchorErrorLog, ComparedValues, ErrorOrigin};
+        use meteora_damm_v2::errors::ErrorCode;
+
+        let left = Pubkey::new_unique();
+        let right = Pubkey::new_unique();
+        let logs = vec![
+            "Program cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG invoke [1]".to_string(),
+            "Program log: AnchorError caused by account: token_a_vault. Error Code: ConstraintHasOne. Error Number: 2001. Error Message: A has one constraint was violated.".to_string(),
+            "Program log: Left:".to_string(),
+            format!("Program log: {left}"),
+            "Program log: Right:".to_string(),
+            format!("Program log: {right}"),
+            "Program cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG failed: custom program error: 0x7d1".to_string(),
+        ];
+
+        let log = AnchorErrorLog::from_logs(&logs).unwrap();
+        assert_eq!(
+            log.origin,
+            Some(ErrorOrigin::AccountName("token_a_vault".to_string()))
+        );
+        assert_eq!(log.error_number, 2001);
+        assert_eq!(
+            log.compared_values,
+            Some(ComparedValues::Pubkeys(left, right))
+        );
+
+        // Built-in Anchor errors are not part of the program's ErrorCode
+        assert_eq!(ErrorCode::from_logs(&logs), None);
+    }
+
+    #[test]
+    fn test_error_from_logs_compared_values() {
+        use anchor_parser::error::{AnchorErrorLog, ComparedValues};
+
+        let logs = [
+            "Program log: AnchorError occurred. Error Code: RequireGteViolated. Error Number: 2505. Error Message: A require_gte expression was violated.",
+            "Program log: Left: 10",
+            "Program log: Right: 20",
+        ];
+
+        let log = AnchorErrorLog::from_logs(logs).unwrap();
+        assert_eq!(log.origin, None);
+        assert_eq!(log.program_id, None);
+        assert_eq!(
+            log.compared_values,
+            Some(ComparedValues::Values("10".to_string(), "20".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_error_from_logs_other_program() {
+        use meteora_damm_v2::errors::ErrorCode;
+
+        let logs = [
+            "Program log: AnchorError occurred. Error Code: ExceededSlippage. Error Number: 6002. Error Message: Exceeded slippage tolerance.",
+            "Program LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo failed: custom program error: 0x1772",
+        ];
+        assert_eq!(ErrorCode::from_logs(logs), None);
+        assert_eq!(ErrorCode::from_logs(["Program log: no error here"]), None);
+    }
+
+    #[test]
+    fn test_error_from_logs_after_cpi_error() {
+        use anchor_parser::error::AnchorErrorLog;
+        use meteora_damm_v2::errors::ErrorCode;
+
+        let logs = [
+            "Program cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG invoke [1]",
+            "Program LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo invoke [2]",
+            "Program log: AnchorError occurred. Error Code: ExceededAmountSlippageTolerance. Error Number: 6003. Error Message: Exceeded amount slippage tolerance.",
+            "Program LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo failed: custom program error: 0x1773",
+            "Program log: AnchorError occurred. Error Code: ExceededSlippage. Error Number: 6002. Error Message: Exceeded slippage tolerance.",
+            "Program cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG failed: custom program error: 0x1772",
+        ];
+
+        let errors = AnchorErrorLog::all_from_logs(logs);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].program_id,
+            Some(solana_sdk::pubkey!(
+                "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo"
+            ))
+        );
+        assert_eq!(errors[1].program_id, Some(meteora_damm_v2::ID));
+        assert_eq!(
+            ErrorCode::from_logs(logs),
+            Some(ErrorCode::ExceededSlippage)
+        );
+    }
+
+    // ── Transaction parsing ─────────────────────────────────────────
+
+    fn sample_claim_position_fee_accounts()
+    -> meteora_damm_v2::instructions::ClaimPositionFeeAccounts {
+        meteora_damm_v2::instructions::ClaimPositionFeeAccounts {
+            pool_authority: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            token_a_account: Pubkey::new_unique(),
+            token_b_account: Pubkey::new_unique(),
+            token_a_vault: Pubkey::new_unique(),
+            token_b_vault: Pubkey::new_unique(),
+            token_a_mint: Pubkey::new_unique(),
+            token_b_mint: Pubkey::new_unique(),
+            position_nft_account: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            token_a_program: Pubkey::new_unique(),
+            token_b_program: Pubkey::new_unique(),
+            event_authority: meteora_damm_v2::events::event_authority(),
+            program: meteora_damm_v2::ID,
+        }
+    }
+
+    fn compile_inner(
+        account_keys: &[Pubkey],
+        ix: &solana_sdk::instruction::Instruction,
+        stack_height: u8,
+    ) -> solana_sdk::message::inner_instruction::InnerInstruction {
+        let index_of = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+        solana_sdk::message::inner_instruction::InnerInstruction {
+            instruction: solana_sdk::message::compiled_instruction::CompiledInstruction {
+                program_id_index: index_of(&ix.program_id),
+                accounts: ix
+                    .accounts
+                    .iter()
+                    .map(|meta| index_of(&meta.pubkey))
+                    .collect(),
+                data: ix.data.clone(),
+            },
+            stack_height,
+        }
+    }
+
+    fn event_cpi_data<E: borsh::BorshSerialize>(discriminator: &[u8], event: &E) -> Vec<u8> {
+        let mut data = anchor_parser::event::EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(discriminator);
+        borsh::BorshSerialize::serialize(event, &mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_parse_transaction() {
+        use anchor_parser::transaction::InnerInstructions;
+        use meteora_damm_v2::events::{EvtClaimPositionFee, EvtSwap2};
+        use meteora_damm_v2::instructions::{self, Instruction, InstructionAccounts};
+        use meteora_damm_v2::utils::{Event, parse_transaction};
+        use solana_sdk::instruction::AccountMeta;
+        use solana_sdk::message::v0::LoadedAddresses;
+        use solana_sdk::message::{AddressLookupTableAccount, VersionedMessage, v0};
+
+        let claim = sample_claim_position_fee_accounts();
+        let claim_ix = instructions::claim_position_fee(&meteora_damm_v2::ID, &claim);
+
+        // A router that CPIs into the program's swap
+        let router = Pubkey::new_unique();
+        let swap_ix = instructions::swap(
+            &meteora_damm_v2::ID,
+            &instructions::SwapAccounts {
+                pool_authority: claim.pool_authority,
+                pool: claim.pool,
+                input_token_account: claim.token_a_account,
+                output_token_account: claim.token_b_account,
+                token_a_vault: claim.token_a_vault,
+                token_b_vault: claim.token_b_vault,
+                token_a_mint: claim.token_a_mint,
+                token_b_mint: claim.token_b_mint,
+                payer: claim.owner,
+                token_a_program: claim.token_a_program,
+                token_b_program: claim.token_b_program,
+                referral_token_account: None,
+                event_authority: claim.event_authority,
+                program: meteora_damm_v2::ID,
+            },
+            meteora_damm_v2::types::SwapParameters {
+                amount_in: 500,
+                minimum_amount_out: 450,
+            },
+        );
+        let bin = Pubkey::new_unique();
+        let mut router_accounts = swap_ix.accounts.clone();
+        router_accounts.push(AccountMeta::new_readonly(meteora_damm_v2::ID, false));
+        router_accounts.push(AccountMeta::new_readonly(bin, false));
+        let router_ix =
+            solana_sdk::instruction::Instruction::new_with_bytes(router, &[9, 9], router_accounts);
+
+        // token_b_mint is loaded from a lookup table
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![claim.token_b_mint],
+        };
+        let message = v0::Message::try_compile(
+            &claim.owner,
+            &[claim_ix, router_ix],
+            &[lookup_table],
+            solana_sdk::hash::Hash::default(),
+        )
+        .unwrap();
+        let loaded = LoadedAddresses {
+            writable: vec![],
+            readonly: vec![claim.token_b_mint],
+        };
+        let tx = solana_sdk::transaction::VersionedTransaction {
+            signatures: vec![solana_sdk::signature::Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+        let account_keys = anchor_parser::transaction::account_keys(&tx.message, &loaded);
+        assert!(
+            !tx.message
+                .static_account_keys()
+                .contains(&claim.token_b_mint)
+        );
+
+        let event_cpi = |data: Vec<u8>| {
+            solana_sdk::instruction::Instruction::new_with_bytes(
+                meteora_damm_v2::ID,
+                &data,
+                vec![AccountMeta::new_readonly(claim.event_authority, true)],
+            )
+        };
+        let claim_event = EvtClaimPositionFee {
+            pool: claim.pool,
+            position: claim.position,
+            owner: claim.owner,
+            fee_a_claimed: 10,
+            fee_b_claimed: 20,
+        };
+        let swap_event = EvtSwap2 {
+            pool: claim.pool,
+            ..Default::default()
+        };
+        let mut swap_with_remaining = swap_ix.clone();
+        swap_with_remaining
+            .accounts
+            .push(AccountMeta::new_readonly(bin, false));
+
+        let inner_instructions = vec![
+            InnerInstructions {
+                index: 0,
+                instructions: vec![compile_inner(
+                    &account_keys,
+                    &event_cpi(event_cpi_data(
+                        &EvtClaimPositionFee::DISCRIMINATOR,
+                        &claim_event,
+                    )),
+                    2,
+                )],
+            },
+            InnerInstructions {
+                index: 1,
+                instructions: vec![
+                    compile_inner(&account_keys, &swap_with_remaining, 2),
+                    compile_inner(
+                        &account_keys,
+                        &event_cpi(event_cpi_data(&EvtSwap2::DISCRIMINATOR, &swap_event)),
+                        3,
+                    ),
+                ],
+            },
+        ];
+
+        let parsed = parse_transaction(&tx, &loaded, &inner_instructions)
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let first = &parsed[0];
+        assert_eq!(
+            (first.index, first.inner_index, first.stack_height),
+            (0, None, 1)
+        );
+        assert!(matches!(
+            first.instruction,
+            Instruction::ClaimPositionFee(_)
+        ));
+        match &first.accounts {
+            InstructionAccounts::ClaimPositionFee(accounts) => {
+                assert_eq!(accounts.position, claim.position);
+                assert_eq!(accounts.token_b_mint, claim.token_b_mint);
+            }
+            other => panic!("unexpected accounts: {other:?}"),
+        }
+        assert!(first.remaining_accounts.is_empty());
+        assert_eq!(first.events.len(), 1);
+        match &first.events[0] {
+            Event::EvtClaimPositionFee(e) => assert_eq!(e.fee_b_claimed, 20),
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        let second = &parsed[1];
+        assert_eq!(
+            (second.index, second.inner_index, second.stack_height),
+            (1, Some(0), 2)
+        );
+        match &second.instruction {
+            Instruction::Swap(args) => {
+                assert_eq!(args._params.amount_in, 500);
+                assert_eq!(args._params.minimum_amount_out, 450);
+            }
+            other => panic!("unexpected instruction: {other:?}"),
+        }
+        match &second.accounts {
+            InstructionAccounts::Swap(accounts) => {
+                assert_eq!(accounts.pool, claim.pool);
+                assert_eq!(accounts.referral_token_account, None);
+            }
+            other => panic!("unexpected accounts: {other:?}"),
+        }
+        assert_eq!(second.remaining_accounts, vec![bin]);
+        assert_eq!(second.events.len(), 1);
+        assert!(matches!(&second.events[0], Event::EvtSwap2(e) if e.pool == claim.pool));
+    }
+
+    #[test]
+    fn test_parse_transaction_ignores_other_programs() {
+        use meteora_damm_v2::utils::parse_transaction;
+        use solana_sdk::message::v0::LoadedAddresses;
+
+        let payer = Pubkey::new_unique();
+        let ix = solana_sdk::instruction::Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![solana_sdk::instruction::AccountMeta::new(payer, true)],
+        );
+        let tx = solana_sdk::transaction::VersionedTransaction::from(
+            solana_sdk::transaction::Transaction::new_with_payer(&[ix], Some(&payer)),
+        );
+        let parsed = parse_transaction(&tx, &LoadedAddresses::default(), &[]);
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn test_parse_transaction_undecodable_instruction() {
+        use meteora_damm_v2::utils::parse_transaction;
+        use solana_sdk::message::v0::LoadedAddresses;
+
+        let claim = sample_claim_position_fee_accounts();
+        // e.g. an instruction added to the program after the IDL
+        let unknown_ix = solana_sdk::instruction::Instruction::new_with_bytes(
+            meteora_damm_v2::ID,
+            &[0; 8],
+            vec![solana_sdk::instruction::AccountMeta::new(claim.owner, true)],
+        );
+        let claim_ix =
+            meteora_damm_v2::instructions::claim_position_fee(&meteora_damm_v2::ID, &claim);
+        let tx = solana_sdk::transaction::VersionedTransaction::from(
+            solana_sdk::transaction::Transaction::new_with_payer(
+                &[unknown_ix, claim_ix],
+                Some(&claim.owner),
+            ),
+        );
+
+        let parsed = parse_transaction(&tx, &LoadedAddresses::default(), &[]);
+        assert_eq!(parsed.len(), 2);
+        let err = parsed[0].as_ref().unwrap_err();
+        assert_eq!((err.index, err.inner_index), (0, None));
+        assert_eq!(err.data, [0; 8]);
+        assert_eq!(
+            err.to_string(),
+            "failed to decode instruction 0: Unknown instruction discriminator"
+        );
+        // The rest of the transaction is still decoded
+        let ix = parsed[1].as_ref().unwrap();
+        assert_eq!(ix.index, 1);
+        assert!(matches!(
+            ix.instruction,
+            meteora_damm_v2::instructions::Instruction::ClaimPositionFee(_)
+        ));
+    }
+
     // ── Types ───────────────────────────────────────────────────────
 
     #[test]
@@ -2929,7 +3473,7 @@ mod tests {
 
         assert_eq!(swap_event.trade_direction, 0);
         assert_eq!(swap_event.collect_fee_mode, 0);
-        assert_eq!(swap_event.has_referral, false);
+        assert!(!swap_event.has_referral);
 
         // --- params ---
         assert_eq!(swap_event.params.amount_0, 1_807_954_327);
@@ -2,6 +2,18 @@ use anchor_parser::declare_program;
 
 declare_program!(meteora_dlmm);
 
+// Only the items a swap indexer needs, and the types they reference.
+declare_program!(
+    dlmm_swaps,
+    path = "idls/meteora_dlmm.json",
+    only(
+        accounts(LbPair, BinArray),
+        events(Swap),
+        instructions(swap),
+        errors
+    ),
+);
+
 #[cfg(test)]
 mod tests {
     use super::meteora_dlmm;
@@ -616,7 +628,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            liquidity_parameter.clone(),
+            liquidity_parameter,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -702,7 +714,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            liquidity_parameter.clone(),
+            liquidity_parameter,
             remaining_accounts_info.clone(),
         );
 
@@ -779,7 +791,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            liquidity_parameter.clone(),
+            liquidity_parameter,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1823,7 +1835,7 @@ mod tests {
         let gen_ix = instructions::for_idl_type_generation_do_not_call(
             &program_id,
             &instructions::ForIdlTypeGenerationDoNotCallAccounts { dummy_zc_account },
-            _ix.clone(),
+            _ix,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2259,7 +2271,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2349,7 +2361,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2503,7 +2515,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            params.clone(),
+            params,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2592,7 +2604,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            ix_data.clone(),
+            ix_data,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2897,7 +2909,7 @@ mod tests {
                 payer,
                 system_program,
             },
-            ix.clone(),
+            ix,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -4400,7 +4412,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            fee_parameter.clone(),
+            fee_parameter,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -4452,7 +4464,7 @@ mod tests {
                 event_authority,
                 program,
             },
-            fee_parameter.clone(),
+            fee_parameter,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -4887,4 +4899,486 @@ mod tests {
         );
         assert_ix(&gen_ix, &expected_ix);
     }
+
+    // ── Instruction decoding ────────────────────────────────────────
+
+    #[test]
+    fn test_decode_swap_instruction() {
+        use meteora_dlmm::instructions::{self, Instruction};
+
+        let mut data = instructions::SwapArgs::DISCRIMINATOR.to_vec();
+        borsh::BorshSerialize::serialize(&1_000_000u64, &mut data).unwrap();
+        borsh::BorshSerialize::serialize(&990_000u64, &mut data).unwrap();
+
+        match Instruction::decode(&data).unwrap() {
+            Instruction::Swap(args) => {
+                assert_eq!(args.amount_in, 1_000_000);
+                assert_eq!(args.min_amount_out, 990_000);
+            }
+            other => panic!("unexpected instruction: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_built_instruction_roundtrip() {
+        use meteora_dlmm::instructions::{self, Instruction};
+        use meteora_dlmm::types;
+
+        let ix = instructions::swap2(
+            &meteora_dlmm::ID,
+            &instructions::Swap2Accounts {
+                lb_pair: Pubkey::new_unique(),
+                bin_array_bitmap_extension: None,
+                reserve_x: Pubkey::new_unique(),
+                reserve_y: Pubkey::new_unique(),
+                user_token_in: Pubkey::new_unique(),
+                user_token_out: Pubkey::new_unique(),
+                token_x_mint: Pubkey::new_unique(),
+                token_y_mint: Pubkey::new_unique(),
+                oracle: Pubkey::new_unique(),
+                host_fee_in: None,
+                user: Pubkey::new_unique(),
+                token_x_program: Pubkey::new_unique(),
+                token_y_program: Pubkey::new_unique(),
+                memo_program: Pubkey::new_unique(),
+                event_authority: Pubkey::new_unique(),
+                program: meteora_dlmm::ID,
+            },
+            42,
+            40,
+            types::RemainingAccountsInfo {
+                slices: vec![types::RemainingAccountsSlice {
+                    accounts_type: types::AccountsType::TransferHookX,
+                    length: 2,
+                }],
+            },
+        );
+
+        match Instruction::decode(&ix.data).unwrap() {
+            Instruction::Swap2(args) => {
+                assert_eq!(args.amount_in, 42);
+                assert_eq!(args.min_amount_out, 40);
+                assert_eq!(args.remaining_accounts_info.slices.len(), 1);
+                assert_eq!(args.remaining_accounts_info.slices[0].length, 2);
+            }
+            other => panic!("unexpected instruction: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_instruction_without_args() {
+        use meteora_dlmm::instructions::{self, Instruction};
+
+        let data = instructions::ClaimFeeArgs::DISCRIMINATOR;
+        assert!(matches!(
+            Instruction::decode(&data).unwrap(),
+            Instruction::ClaimFee(instructions::ClaimFeeArgs)
+        ));
+    }
+
+    #[test]
+    fn test_decode_instruction_errors() {
+        use meteora_dlmm::instructions::{self, Instruction};
+
+        // Unknown discriminator
+        assert!(Instruction::decode(&[0; 16]).is_err());
+        // Too short for a discriminator
+        assert!(Instruction::decode(&[248, 198]).is_err());
+        // Known discriminator, truncated args
+        let mut data = instructions::SwapArgs::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[1, 2, 3]);
+        assert!(Instruction::decode(&data).is_err());
+    }
+
+    // ── Instruction account decoding ────────────────────────────────
+
+    fn sample_swap_accounts() -> meteora_dlmm::instructions::SwapAccounts {
+        meteora_dlmm::instructions::SwapAccounts {
+            lb_pair: Pubkey::new_unique(),
+            bin_array_bitmap_extension: None,
+            reserve_x: Pubkey::new_unique(),
+            reserve_y: Pubkey::new_unique(),
+            user_token_in: Pubkey::new_unique(),
+            user_token_out: Pubkey::new_unique(),
+            token_x_mint: Pubkey::new_unique(),
+            token_y_mint: Pubkey::new_unique(),
+            oracle: Pubkey::new_unique(),
+            host_fee_in: Some(Pubkey::new_unique()),
+            user: Pubkey::new_unique(),
+            token_x_program: Pubkey::new_unique(),
+            token_y_program: Pubkey::new_unique(),
+            event_authority: Pubkey::new_unique(),
+            program: meteora_dlmm::ID,
+        }
+    }
+
+    #[test]
+    fn test_swap_accounts_from_account_metas() {
+        use meteora_dlmm::instructions::{self, SwapAccounts};
+
+        let accounts = sample_swap_accounts();
+        let mut ix = instructions::swap(&meteora_dlmm::ID, &accounts, 1, 0);
+        let bin_array = solana_sdk::instruction::AccountMeta::new(Pubkey::new_unique(), false);
+        ix.accounts.push(bin_array.clone());
+
+        let decoded = SwapAccounts::from_account_metas(&meteora_dlmm::ID, &ix.accounts).unwrap();
+        assert_eq!(SwapAccounts::LEN, 15);
+        assert_eq!(decoded.lb_pair, accounts.lb_pair);
+        assert_eq!(decoded.bin_array_bitmap_extension, None);
+        assert_eq!(decoded.host_fee_in, accounts.host_fee_in);
+        assert_eq!(decoded.user, accounts.user);
+        assert_eq!(decoded.program, meteora_dlmm::ID);
+        assert_eq!(
+            SwapAccounts::remaining_accounts(&ix.accounts),
+            std::slice::from_ref(&bin_array)
+        );
+    }
+
+    #[test]
+    fn test_swap_accounts_from_account_keys() {
+        use meteora_dlmm::instructions::{self, SwapAccounts};
+
+        let accounts = sample_swap_accounts();
+        let ix = instructions::swap(&meteora_dlmm::ID, &accounts, 1, 0);
+        let message = solana_sdk::message::Message::new(&[ix], Some(&accounts.user));
+        let compiled = &message.instructions[0];
+
+        let decoded = SwapAccounts::from_account_keys(
+            &meteora_dlmm::ID,
+            &message.account_keys,
+            &compiled.accounts,
+        )
+        .unwrap();
+        assert_eq!(decoded.lb_pair, accounts.lb_pair);
+        assert_eq!(decoded.reserve_x, accounts.reserve_x);
+        assert_eq!(decoded.oracle, accounts.oracle);
+        assert_eq!(decoded.bin_array_bitmap_extension, None);
+        assert_eq!(decoded.host_fee_in, accounts.host_fee_in);
+        assert_eq!(decoded.event_authority, accounts.event_authority);
+
+        assert!(
+            SwapAccounts::from_account_keys(&meteora_dlmm::ID, &message.account_keys, &[0, 200])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_swap_accounts_other_deployment() {
+        use meteora_dlmm::instructions::{self, SwapAccounts};
+
+        // Absent optional accounts are filled with the builder's program ID,
+        // so decoding must compare against the same one
+        let program_id = Pubkey::new_unique();
+        let accounts = sample_swap_accounts();
+        let ix = instructions::swap(&program_id, &accounts, 1, 0);
+
+        let decoded = SwapAccounts::from_account_metas(&program_id, &ix.accounts).unwrap();
+        assert_eq!(decoded.bin_array_bitmap_extension, None);
+        assert_eq!(decoded.host_fee_in, accounts.host_fee_in);
+
+        let decoded = SwapAccounts::from_account_metas(&meteora_dlmm::ID, &ix.accounts).unwrap();
+        assert_eq!(decoded.bin_array_bitmap_extension, Some(program_id));
+    }
+
+    #[test]
+    fn test_swap_accounts_missing_required_account() {
+        use meteora_dlmm::instructions::{self, SwapAccounts};
+
+        let ix = instructions::swap(&meteora_dlmm::ID, &sample_swap_accounts(), 1, 0);
+        let err =
+            SwapAccounts::from_account_metas(&meteora_dlmm::ID, &ix.accounts[..10]).unwrap_err();
+        assert!(err.to_string().contains("user"));
+        assert!(SwapAccounts::remaining_accounts(&ix.accounts[..10]).is_empty());
+    }
+
+    // ── PDAs ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pda_seeds() {
+        use meteora_dlmm::pda;
+
+        let lb_pair = Pubkey::new_unique();
+        let base = Pubkey::new_unique();
+        assert_eq!(
+            pda::find_position(&lb_pair, &base, -35, 70),
+            Pubkey::find_program_address(
+                &[
+                    b"position",
+                    lb_pair.as_ref(),
+                    base.as_ref(),
+                    &(-35i32).to_le_bytes(),
+                    &70i32.to_le_bytes(),
+                ],
+                &meteora_dlmm::ID
+            )
+        );
+        assert_eq!(
+            pda::find_bin_array(&lb_pair, -1),
+            Pubkey::find_program_address(
+                &[b"bin_array", lb_pair.as_ref(), &(-1i64).to_le_bytes()],
+                &meteora_dlmm::ID
+            )
+        );
+        // Field of a struct argument (`ix.index`)
+        assert_eq!(
+            pda::find_preset_parameter(3),
+            Pubkey::find_program_address(
+                &[b"preset_parameter2", &3u16.to_le_bytes()],
+                &meteora_dlmm::ID
+            )
+        );
+    }
+
+    // ── Zero-copy accounts ──────────────────────────────────────────
+
+    /// 8-byte discriminator followed by a zeroed `BinArray`, positioned so
+    /// the payload starts at an address with the given offset from
+    /// `BinArray`'s alignment. Returns the backing buffer and the range.
+    fn bin_array_buffer(misalign: usize) -> (Vec<u128>, std::ops::Range<usize>) {
+        use meteora_dlmm::accounts::BinArray;
+
+        let align = std::mem::align_of::<BinArray>();
+        let len = 8 + std::mem::size_of::<BinArray>();
+        let mut buffer = vec![0u128; (len + 2 * align) / 16 + 1];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+        let start = (align - 8 % align) % align + misalign;
+        bytes[start..start + 8].copy_from_slice(&BinArray::DISCRIMINATOR);
+        (buffer, start..start + len)
+    }
+
+    #[test]
+    fn test_from_account_data_ref_borrows() {
+        use meteora_dlmm::accounts::BinArray;
+
+        let (mut buffer, range) = bin_array_buffer(0);
+        let data = &mut bytemuck::cast_slice_mut::<u128, u8>(&mut buffer)[range];
+        data[8..16].copy_from_slice(&7i64.to_le_bytes());
+
+        let bin_array = BinArray::from_account_data_ref(data).unwrap();
+        assert_eq!(bin_array.index, 7);
+        assert_eq!(
+            bin_array as *const BinArray as *const u8,
+            data[8..].as_ptr()
+        );
+
+        let bin_array = BinArray::from_account_data_mut(data).unwrap();
+        bin_array.index = -3;
+        bin_array.bins[69].amount_x = 1_000;
+        assert_eq!(data[8..16], (-3i64).to_le_bytes());
+        assert_eq!(
+            BinArray::from_account_data(data).unwrap().bins[69].amount_x,
+            1_000
+        );
+    }
+
+    #[test]
+    fn test_from_account_data_ref_rejects_misaligned_data() {
+        use meteora_dlmm::accounts::BinArray;
+
+        let (mut buffer, range) = bin_array_buffer(1);
+        let data = &mut bytemuck::cast_slice_mut::<u128, u8>(&mut buffer)[range];
+
+        let err = BinArray::from_account_data_ref(data).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        let align = std::mem::align_of::<BinArray>();
+        let msg = err.to_string();
+        assert!(msg.contains("not aligned"), "{msg}");
+        assert!(msg.contains(&format!("{align}-byte alignment")), "{msg}");
+        assert!(msg.contains(&format!("offset 1 modulo {align}")), "{msg}");
+        assert!(BinArray::from_account_data_mut(data).is_err());
+        // Copying still works
+        assert!(BinArray::from_account_data(data).is_ok());
+    }
+
+    #[test]
+    fn test_from_account_data_ref_checks_discriminator_and_length() {
+        use meteora_dlmm::accounts::BinArray;
+
+        let (mut buffer, range) = bin_array_buffer(0);
+        let data = &mut bytemuck::cast_slice_mut::<u128, u8>(&mut buffer)[range];
+
+        let err = BinArray::from_account_data_ref(&data[..data.len() - 1]).unwrap_err();
+        assert_eq!(err.to_string(), "Account data too short for bytemuck type");
+
+        data[0] ^= 1;
+        let err = BinArray::from_account_data_ref(data).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid account discriminator");
+    }
+
+    #[test]
+    fn test_bytemuck_account_to_account_data() {
+        use meteora_dlmm::accounts::BinArray;
+
+        let (mut buffer, range) = bin_array_buffer(3);
+        let data = &mut bytemuck::cast_slice_mut::<u128, u8>(&mut buffer)[range];
+        data[8..16].copy_from_slice(&42i64.to_le_bytes());
+        let len = data.len();
+
+        let mut bin_array = BinArray::from_account_data(data).unwrap();
+        assert_eq!(bin_array.to_account_data().unwrap(), data);
+
+        bin_array.bins[0].amount_y = 9;
+        let modified = bin_array.to_account_data_padded(len + 16).unwrap();
+        assert_eq!(modified.len(), len + 16);
+        let parsed = BinArray::from_account_data(&modified).unwrap();
+        assert_eq!(parsed.index, 42);
+        assert_eq!(parsed.bins[0].amount_y, 9);
+    }
+
+    // ── Account sizes ───────────────────────────────────────────────
+
+    #[test]
+    fn test_bytemuck_account_size() {
+        use meteora_dlmm::accounts::{BinArray, LbPair, PresetParameter};
+
+        // Sizes of the accounts on mainnet
+        assert_eq!(BinArray::SIZE, 10_136);
+        assert_eq!(LbPair::SIZE, 904);
+        // Borsh accounts get INIT_SPACE instead
+        assert_eq!(
+            8 + PresetParameter::INIT_SPACE,
+            PresetParameter::default().serialized_len().unwrap()
+        );
+    }
+
+    // ── Field offsets & filters ─────────────────────────────────────
+
+    #[test]
+    fn test_bytemuck_field_offsets() {
+        use meteora_dlmm::accounts::LbPair;
+
+        assert_eq!(LbPair::PARAMETERS_OFFSET, 8);
+        assert_eq!(LbPair::ACTIVE_ID_OFFSET, 76);
+        assert_eq!(LbPair::TOKEN_X_MINT_OFFSET, 88);
+        assert_eq!(LbPair::TOKEN_Y_MINT_OFFSET, 120);
+    }
+
+    #[test]
+    fn test_memcmp_filters() {
+        use meteora_dlmm::accounts::LbPair;
+        use solana_client::rpc_filter::RpcFilterType;
+
+        let mut lb_pair: LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.token_x_mint = Pubkey::new_unique();
+        lb_pair.active_id = -42;
+        let data = lb_pair.to_account_data().unwrap();
+
+        let matches = |filter: RpcFilterType| match filter {
+            RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&data),
+            _ => panic!("expected a memcmp filter"),
+        };
+        assert!(matches(LbPair::discriminator_filter()));
+        assert!(matches(LbPair::filter_token_x_mint(&lb_pair.token_x_mint)));
+        assert!(!matches(LbPair::filter_token_x_mint(&Pubkey::new_unique())));
+        assert!(matches(LbPair::filter_active_id(&-42)));
+        assert!(!matches(LbPair::filter_active_id(&42)));
+    }
+
+    #[test]
+    fn test_bytemuck_read_field() {
+        use meteora_dlmm::accounts::LbPair;
+
+        let mut lb_pair: LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.token_y_mint = Pubkey::new_unique();
+        lb_pair.active_id = -42;
+        lb_pair.parameters.base_factor = 10_000;
+        // Unaligned on purpose: fields are copied out, not borrowed
+        let mut buffer = vec![0u8];
+        buffer.extend(lb_pair.to_account_data().unwrap());
+        let data = &buffer[1..];
+
+        assert_eq!(LbPair::read_active_id(data).unwrap(), -42);
+        assert_eq!(
+            LbPair::read_token_y_mint(data).unwrap(),
+            lb_pair.token_y_mint
+        );
+        assert_eq!(LbPair::read_parameters(data).unwrap().base_factor, 10_000);
+
+        let err = LbPair::read_active_id(&data[..data.len() - 1]).unwrap_err();
+        assert_eq!(err.to_string(), "Account data too short for bytemuck type");
+        let mut data = data.to_vec();
+        data[0] ^= 1;
+        let err = LbPair::read_active_id(&data).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid account discriminator");
+    }
+
+    // ── Selective generation ────────────────────────────────────────
+
+    #[test]
+    fn test_only_selected_accounts() {
+        use super::dlmm_swaps::accounts::{BinArray, LbPair};
+        use super::dlmm_swaps::utils::Account;
+
+        assert_eq!(
+            LbPair::DISCRIMINATOR,
+            meteora_dlmm::accounts::LbPair::DISCRIMINATOR
+        );
+
+        let mut data = BinArray::DISCRIMINATOR.to_vec();
+        data.resize(10_136, 0);
+        // Exhaustive: the enum has no other variants
+        match Account::parse(&data).unwrap() {
+            Account::BinArray(bin_array) => assert_eq!(bin_array.bins.len(), 70),
+            Account::LbPair(_) => panic!("expected BinArray"),
+        }
+    }
+
+    #[test]
+    fn test_only_selected_events() {
+        use super::dlmm_swaps::events::Swap;
+        use super::dlmm_swaps::utils::Event;
+        use borsh::BorshSerialize;
+
+        let swap = Swap {
+            lb_pair: Pubkey::new_unique(),
+            from: Pubkey::new_unique(),
+            start_bin_id: -3,
+            end_bin_id: 2,
+            amount_in: 1_000,
+            amount_out: 990,
+            swap_for_y: true,
+            fee: 10,
+            protocol_fee: 1,
+            fee_bps: 25,
+            host_fee: 0,
+        };
+        let mut data = Swap::DISCRIMINATOR.to_vec();
+        swap.serialize(&mut data).unwrap();
+
+        match Event::decode(&data).unwrap() {
+            Event::Swap(decoded) => assert_eq!(decoded.amount_out, 990),
+        }
+    }
+
+    #[test]
+    fn test_only_referenced_types() {
+        use super::dlmm_swaps::types::{Bin, StaticParameters, VariableParameters};
+
+        let _: fn(
+            &super::dlmm_swaps::accounts::LbPair,
+        ) -> (&StaticParameters, &VariableParameters) =
+            |pair| (&pair.parameters, &pair.v_parameters);
+        let _: fn(&super::dlmm_swaps::accounts::BinArray) -> &Bin = |array| &array.bins[0];
+    }
+
+    #[test]
+    fn test_only_selected_instructions() {
+        use super::dlmm_swaps::errors::ErrorCode;
+        use super::dlmm_swaps::instructions::{Instruction, SwapArgs};
+
+        assert_eq!(
+            SwapArgs::DISCRIMINATOR,
+            meteora_dlmm::instructions::SwapArgs::DISCRIMINATOR
+        );
+        let mut data = SwapArgs::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data.extend_from_slice(&90u64.to_le_bytes());
+        match Instruction::decode(&data).unwrap() {
+            Instruction::Swap(args) => assert_eq!(args.min_amount_out, 90),
+        }
+        assert_eq!(
+            ErrorCode::ExceededAmountSlippageTolerance.code(),
+            meteora_dlmm::errors::ErrorCode::ExceededAmountSlippageTolerance.code()
+        );
+    }
 }
@@ -0,0 +1,157 @@
+use anchor_parser::num::{I256, ParseIntError, U256};
+
+// ── Conversions ─────────────────────────────────────────────────────
+
+#[test]
+fn test_le_bytes_roundtrip() {
+    let mut bytes = [0; 32];
+    bytes[0] = 1;
+    bytes[31] = 2;
+    let value = U256::from_le_bytes(bytes);
+    assert_eq!(value.to_le_bytes(), bytes);
+    assert_eq!(value.to_be_bytes()[0], 2);
+    assert_eq!(U256::from_be_bytes(value.to_be_bytes()), value);
+
+    assert_eq!(I256::from(-1i8).to_le_bytes(), [0xff; 32]);
+    assert_eq!(I256::from_le_bytes([0xff; 32]), I256::from(-1i64));
+}
+
+#[test]
+fn test_primitive_conversions() {
+    assert_eq!(u128::try_from(U256::from(u128::MAX)), Ok(u128::MAX));
+    assert!(u128::try_from(U256::MAX).is_err());
+    assert!(u64::try_from(U256::from(u128::from(u64::MAX) + 1)).is_err());
+    assert!(U256::try_from(-1i64).is_err());
+
+    assert_eq!(i128::try_from(I256::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(i128::try_from(I256::from(-5i32)), Ok(-5));
+    assert!(i128::try_from(I256::from(u128::MAX)).is_err());
+    assert!(i128::try_from(I256::MIN).is_err());
+}
+
+#[test]
+fn test_borsh_and_bytemuck_layout() {
+    let value = I256::from(-2i64);
+    let encoded = borsh::to_vec(&value).unwrap();
+    assert_eq!(encoded.len(), 32);
+    assert_eq!(borsh::from_slice::<I256>(&encoded).unwrap(), value);
+
+    assert_eq!(std::mem::align_of::<U256>(), 1);
+    let value = U256::from(0x0102u16);
+    assert_eq!(&bytemuck::bytes_of(&value)[..3], [2, 1, 0]);
+}
+
+// ── Decimal strings ─────────────────────────────────────────────────
+
+#[test]
+fn test_display() {
+    assert_eq!(U256::ZERO.to_string(), "0");
+    assert_eq!(
+        U256::from(10_000_000_000_000_000_000u64).to_string(),
+        "10000000000000000000"
+    );
+    assert_eq!(
+        U256::MAX.to_string(),
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+    );
+    assert_eq!(
+        I256::MIN.to_string(),
+        "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+    );
+    assert_eq!(format!("{:>5}", I256::from(-7i8)), "   -7");
+    assert_eq!(format!("{:?}", U256::from(42u8)), "42");
+}
+
+#[test]
+fn test_parse() {
+    for s in [
+        "0",
+        "18446744073709551616",
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+    ] {
+        assert_eq!(s.parse::<U256>().unwrap().to_string(), s);
+    }
+    for s in [
+        "-1",
+        "57896044618658097711785492504343953926634992332820282019728792003956564819967",
+        "-57896044618658097711785492504343953926634992332820282019728792003956564819968",
+    ] {
+        assert_eq!(s.parse::<I256>().unwrap().to_string(), s);
+    }
+
+    assert_eq!("".parse::<U256>(), Err(ParseIntError::InvalidDigit));
+    assert_eq!("-1".parse::<U256>(), Err(ParseIntError::InvalidDigit));
+    assert_eq!("1_000".parse::<U256>(), Err(ParseIntError::InvalidDigit));
+    assert_eq!(
+        "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+            .parse::<U256>(),
+        Err(ParseIntError::Overflow)
+    );
+    assert_eq!(
+        "57896044618658097711785492504343953926634992332820282019728792003956564819968"
+            .parse::<I256>(),
+        Err(ParseIntError::Overflow)
+    );
+}
+
+// ── Arithmetic ──────────────────────────────────────────────────────
+
+#[test]
+fn test_unsigned_checked_arithmetic() {
+    let a = U256::from(u128::MAX);
+    let sum = a.checked_add(U256::ONE).unwrap();
+    assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+    assert_eq!(sum.checked_sub(U256::ONE), Some(a));
+    assert_eq!(U256::ZERO.checked_sub(U256::ONE), None);
+    assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+
+    let square = a.checked_mul(a).unwrap();
+    assert_eq!(
+        square.to_string(),
+        "115792089237316195423570985008687907852589419931798687112530834793049593217025"
+    );
+    assert_eq!(square.checked_div(a), Some(a));
+    assert_eq!(square.checked_mul(U256::from(2u8)), None);
+    assert_eq!(
+        U256::from(100u8).checked_rem(U256::from(7u8)),
+        Some(U256::from(2u8))
+    );
+    assert_eq!(U256::ONE.checked_div(U256::ZERO), None);
+}
+
+#[test]
+fn test_signed_checked_arithmetic() {
+    let minus_seven = I256::from(-7i8);
+    let two = I256::from(2u8);
+    assert_eq!(minus_seven.checked_add(two), Some(I256::from(-5i8)));
+    assert_eq!(minus_seven.checked_mul(two), Some(I256::from(-14i8)));
+    assert_eq!(minus_seven.checked_div(two), Some(I256::from(-3i8)));
+    assert_eq!(minus_seven.checked_rem(two), Some(I256::from(-1i8)));
+    assert_eq!(minus_seven.checked_neg(), Some(I256::from(7u8)));
+
+    assert_eq!(I256::MAX.checked_add(I256::ONE), None);
+    assert_eq!(I256::MIN.checked_sub(I256::ONE), None);
+    assert_eq!(I256::MIN.checked_neg(), None);
+    assert_eq!(I256::MIN.checked_div(I256::from(-1i8)), None);
+    assert_eq!(I256::MIN.checked_mul(I256::from(-1i8)), None);
+    assert_eq!(I256::MIN.checked_mul(I256::ONE), Some(I256::MIN));
+    assert_eq!(
+        I256::MIN.unsigned_abs().to_string(),
+        I256::MIN.to_string()[1..]
+    );
+}
+
+#[test]
+fn test_ordering() {
+    assert!(U256::MAX > U256::from(u128::MAX));
+    assert!(I256::MIN < I256::from(-1i8));
+    assert!(I256::from(-1i8) < I256::ZERO);
+    assert!(I256::ZERO < I256::MAX);
+
+    let mut values = [I256::from(3u8), I256::MIN, I256::from(-3i8), I256::MAX];
+    values.sort();
+    assert_eq!(
+        values,
+        [I256::MIN, I256::from(-3i8), I256::from(3u8), I256::MAX]
+    );
+}
@@ -91,6 +91,163 @@ mod tests {
         assert!(pumpfun::accounts::BondingCurve::from_account_data(&data).is_err());
     }
 
+    // ── Account to_account_data ─────────────────────────────────────
+
+    fn bonding_curve() -> pumpfun::accounts::BondingCurve {
+        pumpfun::accounts::BondingCurve {
+            virtual_token_reserves: 1_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            real_sol_reserves: 0,
+            token_total_supply: 1_000_000_000_000_000,
+            complete: false,
+            creator: Pubkey::new_unique(),
+            is_mayhem_mode: true,
+        }
+    }
+
+    #[test]
+    fn test_bonding_curve_to_account_data() {
+        use borsh::BorshSerialize;
+        use pumpfun::accounts::BondingCurve;
+
+        let bc = bonding_curve();
+        let mut expected = BondingCurve::DISCRIMINATOR.to_vec();
+        bc.serialize(&mut expected).unwrap();
+
+        let data = bc.to_account_data().unwrap();
+        assert_eq!(data, expected);
+        let parsed = BondingCurve::from_account_data(&data).unwrap();
+        assert_eq!(parsed.creator, bc.creator);
+        assert!(parsed.is_mayhem_mode);
+    }
+
+    #[test]
+    fn test_bonding_curve_to_account_data_padded() {
+        let bc = bonding_curve();
+        let unpadded = bc.to_account_data().unwrap();
+
+        let data = bc.to_account_data_padded(150).unwrap();
+        assert_eq!(data.len(), 150);
+        assert_eq!(data[..unpadded.len()], unpadded);
+        assert!(data[unpadded.len()..].iter().all(|&b| b == 0));
+
+        let err = bc.to_account_data_padded(unpadded.len() - 1).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_account_enum_to_account_data() {
+        use anchor_parser::AccountSerialize;
+        use pumpfun::utils::Account;
+
+        let bc = bonding_curve();
+        let account = Account::BondingCurve(bc);
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        assert_eq!(data, bc.to_account_data().unwrap());
+        assert!(matches!(
+            Account::parse(&data),
+            Ok(Account::BondingCurve(_))
+        ));
+    }
+
+    // ── Account sizes ───────────────────────────────────────────────
+
+    #[test]
+    fn test_fixed_size_account_init_space() {
+        use pumpfun::accounts::BondingCurve;
+
+        assert_eq!(BondingCurve::INIT_SPACE, 74);
+        let bc = bonding_curve();
+        assert_eq!(bc.serialized_len().unwrap(), 8 + BondingCurve::INIT_SPACE);
+        assert_eq!(
+            bc.serialized_len().unwrap(),
+            bc.to_account_data().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_variable_size_account_serialized_len() {
+        use pumpfun::accounts::FeeConfig;
+        use pumpfun::types::FeeTier;
+
+        let mut config = FeeConfig::default();
+        assert_eq!(config.serialized_len().unwrap(), 8 + 1 + 32 + 24 + 4);
+
+        config.fee_tiers = vec![FeeTier::default(); 2];
+        assert_eq!(
+            config.serialized_len().unwrap(),
+            8 + 1 + 32 + 24 + 4 + 2 * 40
+        );
+        assert_eq!(
+            config.serialized_len().unwrap(),
+            config.to_account_data().unwrap().len()
+        );
+    }
+
+    // ── Field offsets & filters ─────────────────────────────────────
+
+    #[test]
+    fn test_borsh_field_offsets() {
+        use pumpfun::accounts::{BondingCurve, FeeConfig};
+
+        assert_eq!(BondingCurve::VIRTUAL_TOKEN_RESERVES_OFFSET, 8);
+        assert_eq!(BondingCurve::COMPLETE_OFFSET, 48);
+        assert_eq!(BondingCurve::CREATOR_OFFSET, 49);
+        // Known up to the first variable-size field
+        assert_eq!(FeeConfig::FEE_TIERS_OFFSET, 8 + 1 + 32 + 24);
+    }
+
+    #[test]
+    fn test_borsh_memcmp_filters() {
+        use pumpfun::accounts::BondingCurve;
+        use solana_client::rpc_filter::RpcFilterType;
+
+        let bc = bonding_curve();
+        let data = bc.to_account_data().unwrap();
+        let matches = |filter: RpcFilterType| match filter {
+            RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&data),
+            _ => panic!("expected a memcmp filter"),
+        };
+
+        assert!(matches(BondingCurve::discriminator_filter()));
+        assert!(matches(BondingCurve::filter_creator(&bc.creator)));
+        assert!(matches(BondingCurve::filter_complete(&false)));
+        assert!(!matches(BondingCurve::filter_complete(&true)));
+        assert!(matches(BondingCurve::filter_real_sol_reserves(&0)));
+    }
+
+    #[test]
+    fn test_borsh_read_field() {
+        use pumpfun::accounts::{BondingCurve, FeeConfig};
+        use pumpfun::types::FeeTier;
+
+        let bc = bonding_curve();
+        let data = bc.to_account_data().unwrap();
+        assert_eq!(BondingCurve::read_creator(&data).unwrap(), bc.creator);
+        assert!(!BondingCurve::read_complete(&data).unwrap());
+        assert_eq!(
+            BondingCurve::read_virtual_sol_reserves(&data).unwrap(),
+            30_000_000_000
+        );
+        assert!(BondingCurve::read_creator(&data[..BondingCurve::CREATOR_OFFSET + 31]).is_err());
+        assert_eq!(
+            BondingCurve::read_creator(&FeeConfig::DISCRIMINATOR)
+                .unwrap_err()
+                .to_string(),
+            "Invalid account discriminator"
+        );
+
+        // A variable-size field at a known offset reads to its end
+        let config = FeeConfig {
+            fee_tiers: vec![FeeTier::default(); 3],
+            ..Default::default()
+        };
+        let data = config.to_account_data().unwrap();
+        assert_eq!(FeeConfig::read_fee_tiers(&data).unwrap().len(), 3);
+    }
+
     // ── Account enum (utils) ────────────────────────────────────────
 
     #[test]
@@ -422,6 +579,241 @@ mod tests {
         }
     }
 
+    // ── Event from_program_logs ─────────────────────────────────────
+
+    fn sample_trade_event(sol_amount: u64) -> pumpfun::events::TradeEvent {
+        pumpfun::events::TradeEvent {
+            mint: Pubkey::new_unique(),
+            sol_amount,
+            token_amount: 50_000_000_000,
+            is_buy: true,
+            user: Pubkey::new_unique(),
+            timestamp: 1700000000,
+            ix_name: "buy".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn program_data_line<E: borsh::BorshSerialize>(discriminator: &[u8], event: &E) -> String {
+        use base64::Engine;
+        let mut data = discriminator.to_vec();
+        borsh::BorshSerialize::serialize(event, &mut data).unwrap();
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(&data)
+        )
+    }
+
+    #[test]
+    fn test_trade_event_from_program_logs() {
+        use pumpfun::events::TradeEvent;
+        use pumpfun::utils::Event;
+
+        let direct = program_data_line(&TradeEvent::DISCRIMINATOR, &sample_trade_event(1));
+        let via_cpi = program_data_line(&TradeEvent::DISCRIMINATOR, &sample_trade_event(2));
+        // Same discriminator and layout, emitted by another program
+        let foreign = program_data_line(&TradeEvent::DISCRIMINATOR, &sample_trade_event(3));
+
+        let logs: Vec<&str> = vec![
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [1]",
+            "Program log: Instruction: Buy",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            &direct,
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P consumed 30000 of 200000 compute units",
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            &foreign,
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [2]",
+            &via_cpi,
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+        ];
+
+        // from_logs picks up every matching line
+        assert_eq!(TradeEvent::from_logs(&logs).len(), 3);
+
+        let events = TradeEvent::from_program_logs(&logs);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event.sol_amount, 1);
+        assert_eq!(
+            (
+                events[0].depth,
+                events[0].instruction_index,
+                events[0].log_index
+            ),
+            (1, 1, 6)
+        );
+        assert_eq!(events[1].event.sol_amount, 2);
+        assert_eq!(
+            (
+                events[1].depth,
+                events[1].instruction_index,
+                events[1].log_index
+            ),
+            (2, 2, 12)
+        );
+
+        let events = Event::from_program_logs(&logs);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[1].event, Event::TradeEvent(e) if e.sol_amount == 2));
+    }
+
+    #[test]
+    fn test_event_from_program_logs_after_failed_cpi() {
+        use pumpfun::events::TradeEvent;
+
+        let data = program_data_line(&TradeEvent::DISCRIMINATOR, &sample_trade_event(7));
+        let logs: Vec<&str> = vec![
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [1]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+            &data,
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA failed: custom program error: 0x1",
+            &data,
+        ];
+
+        let events = TradeEvent::from_program_logs(&logs);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].log_index, 4);
+        assert_eq!(events[0].depth, 1);
+    }
+
+    #[test]
+    fn test_event_iter_logs() {
+        use pumpfun::events::{CompleteEvent, TradeEvent};
+        use pumpfun::utils::Event;
+
+        let trade = program_data_line(&TradeEvent::DISCRIMINATOR, &sample_trade_event(5));
+        let complete = program_data_line(
+            &CompleteEvent::DISCRIMINATOR,
+            &CompleteEvent {
+                user: Pubkey::new_unique(),
+                mint: Pubkey::new_unique(),
+                bonding_curve: Pubkey::new_unique(),
+                timestamp: 1700000000,
+            },
+        );
+        let unknown = program_data_line(&[9u8; 8], &sample_trade_event(6));
+        let logs = vec![
+            "Program log: Instruction: Buy".to_string(),
+            unknown,
+            trade.clone(),
+            "Program data: not base64!".to_string(),
+            complete,
+            trade,
+        ];
+
+        let mut iter = Event::iter_logs(&logs);
+        assert!(matches!(iter.next(), Some(Event::TradeEvent(e)) if e.sol_amount == 5));
+        assert!(matches!(iter.next(), Some(Event::CompleteEvent(_))));
+        assert!(matches!(iter.next(), Some(Event::TradeEvent(_))));
+        assert!(iter.next().is_none());
+
+        let trades: Vec<TradeEvent> = TradeEvent::iter_logs(&logs).collect();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades.len(), TradeEvent::from_logs(&logs).len());
+    }
+
+    // ── Event try_from_logs ─────────────────────────────────────────
+
+    #[test]
+    fn test_event_try_from_logs_reports_failures() {
+        use anchor_parser::event::EventDecodeError;
+        use base64::Engine;
+        use pumpfun::events::TradeEvent;
+        use pumpfun::utils::Event;
+
+        let valid = program_data_line(&TradeEvent::DISCRIMINATOR, &sample_trade_event(1));
+        // Layout changed: payload truncated
+        let mut truncated = TradeEvent::DISCRIMINATOR.to_vec();
+        truncated.extend_from_slice(&[1, 2, 3]);
+        let truncated = format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(&truncated)
+        );
+        let unknown = format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode([9u8; 12])
+        );
+        let logs = [
+            "Program log: Instruction: Buy",
+            valid.as_str(),
+            truncated.as_str(),
+            unknown.as_str(),
+        ];
+
+        // from_logs silently drops the failures
+        assert_eq!(Event::from_logs(logs).len(), 1);
+
+        let results = Event::try_from_logs(logs);
+        assert_eq!(results.len(), 3);
+        assert!(matches!(&results[0], Ok(Event::TradeEvent(e)) if e.sol_amount == 1));
+        match &results[1] {
+            Err(EventDecodeError::Deserialize {
+                index,
+                discriminator,
+                data,
+                ..
+            }) => {
+                assert_eq!(*index, 2);
+                assert_eq!(discriminator.as_slice(), TradeEvent::DISCRIMINATOR);
+                assert_eq!(data.as_slice(), [1, 2, 3]);
+            }
+            other => panic!("Expected Deserialize error, got {:?}", other),
+        }
+        match &results[2] {
+            Err(EventDecodeError::Unknown {
+                index,
+                discriminator,
+                data,
+            }) => {
+                assert_eq!(*index, 3);
+                assert_eq!(discriminator.as_slice(), [9; 8]);
+                assert_eq!(data.as_slice(), [9; 4]);
+            }
+            other => panic!("Expected Unknown error, got {:?}", other),
+        }
+
+        // The per-event variant only reports lines with its discriminator
+        let results = TradeEvent::try_from_logs(logs);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert_eq!(results[1].as_ref().unwrap_err().index(), 2);
+    }
+
+    #[test]
+    fn test_event_try_from_cpi_logs_reports_failures() {
+        use anchor_parser::event::{EVENT_IX_TAG, EventDecodeError};
+        use pumpfun::events::TradeEvent;
+        use pumpfun::utils::Event;
+
+        let mut valid = EVENT_IX_TAG.to_vec();
+        valid.extend_from_slice(&TradeEvent::DISCRIMINATOR);
+        borsh::BorshSerialize::serialize(&sample_trade_event(7), &mut valid).unwrap();
+        let mut truncated = EVENT_IX_TAG.to_vec();
+        truncated.extend_from_slice(&TradeEvent::DISCRIMINATOR);
+        let entries = [
+            bs58::encode(&valid).into_string(),
+            bs58::encode(&truncated).into_string(),
+            // Not an emit_cpi! entry
+            bs58::encode([0u8; 24]).into_string(),
+        ];
+
+        let results = Event::try_from_cpi_logs(&entries);
+        assert_eq!(results.len(), 2);
+        assert!(matches!(&results[0], Ok(Event::TradeEvent(e)) if e.sol_amount == 7));
+        let err = results[1].as_ref().unwrap_err();
+        assert!(matches!(
+            err,
+            EventDecodeError::Deserialize { index: 1, .. }
+        ));
+        assert!(std::error::Error::source(err).is_some());
+
+        assert_eq!(TradeEvent::try_from_cpi_logs(&entries).len(), 2);
+    }
+
     // ── Instructions ────────────────────────────────────────────────
 
     #[test]
@@ -672,7 +1064,7 @@ mod tests {
             },
             amount,
             max_sol_cost,
-            track_volume.clone(),
+            track_volume,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -757,7 +1149,7 @@ mod tests {
             },
             spendable_sol_in,
             min_tokens_out,
-            track_volume.clone(),
+            track_volume,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -1931,6 +2323,262 @@ mod tests {
         assert_ix(&gen_ix, &expected_ix);
     }
 
+    // ── Return data ─────────────────────────────────────────────────
+
+    fn sample_minimum_distributable_fee() -> pumpfun::events::MinimumDistributableFeeEvent {
+        pumpfun::events::MinimumDistributableFeeEvent {
+            minimum_required: 5_000,
+            distributable_fees: 42,
+            can_distribute: false,
+        }
+    }
+
+    #[test]
+    fn test_decode_return_restores_trimmed_zeros() {
+        use pumpfun::instructions::get_minimum_distributable_fee;
+
+        let expected = sample_minimum_distributable_fee();
+        let mut data = borsh::to_vec(&expected).unwrap();
+        let decoded = get_minimum_distributable_fee::decode_return(&data).unwrap();
+        assert_eq!(decoded.minimum_required, 5_000);
+        assert_eq!(decoded.distributable_fees, 42);
+
+        // The runtime trims trailing zero bytes (here `can_distribute` and the
+        // upper bytes of `distributable_fees`)
+        while data.last() == Some(&0) {
+            data.pop();
+        }
+        assert_eq!(data.len(), 9);
+        let decoded: get_minimum_distributable_fee::Return =
+            get_minimum_distributable_fee::decode_return(&data).unwrap();
+        assert_eq!(decoded.distributable_fees, 42);
+        assert!(!decoded.can_distribute);
+    }
+
+    #[test]
+    fn test_decode_return_log() {
+        use base64::Engine;
+        use pumpfun::instructions::get_minimum_distributable_fee;
+
+        let data = borsh::to_vec(&sample_minimum_distributable_fee()).unwrap();
+        let return_line = format!(
+            "Program return: {} {}",
+            pumpfun::ID,
+            base64::engine::general_purpose::STANDARD.encode(&data)
+        );
+        let foreign_line = format!("Program return: {} AQ==", Pubkey::new_unique());
+        let logs = vec![
+            format!("Program {} invoke [1]", pumpfun::ID),
+            return_line,
+            format!("Program {} success", pumpfun::ID),
+            foreign_line,
+        ];
+
+        let decoded = get_minimum_distributable_fee::decode_return_log(&logs)
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.minimum_required, 5_000);
+
+        assert!(get_minimum_distributable_fee::decode_return_log(&logs[..1]).is_none());
+    }
+
+    #[test]
+    fn test_decode_return_data_checks_program() {
+        use pumpfun::instructions::get_minimum_distributable_fee;
+
+        let data = borsh::to_vec(&sample_minimum_distributable_fee()).unwrap();
+        let decoded =
+            get_minimum_distributable_fee::decode_return_data(&pumpfun::ID, &data).unwrap();
+        assert_eq!(decoded.distributable_fees, 42);
+
+        let err = get_minimum_distributable_fee::decode_return_data(&Pubkey::new_unique(), &data)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_decode_return_data_bogus_length_prefix() {
+        use anchor_parser::return_data::{MAX_RETURN_DATA, decode};
+
+        // Length prefixes far past the padding fail instead of reading
+        // gigabytes of zeros
+        let err = decode::<Vec<u8>>(&[0xff, 0xff, 0xff, 0xff]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(decode::<String>(&[0xff, 0xff, 0xff, 0x7f]).is_err());
+        assert!(decode::<Vec<[u8; 0]>>(&[0xff, 0xff, 0xff, 0xff]).is_err());
+
+        // Zero padding still fills up to the maximum return data size
+        let len = (MAX_RETURN_DATA as u32 - 4).to_le_bytes();
+        assert_eq!(
+            decode::<Vec<u8>>(&len).unwrap(),
+            vec![0; MAX_RETURN_DATA - 4]
+        );
+    }
+
+    // ── PDAs ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pda_const_seeds() {
+        use pumpfun::pda;
+
+        let (global, _) = pda::find_global();
+        assert_eq!(
+            global.to_string(),
+            "4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf"
+        );
+        let (event_authority, _) = pda::find_event_authority();
+        assert_eq!(event_authority, pumpfun::events::event_authority());
+    }
+
+    #[test]
+    fn test_pda_account_seeds() {
+        use pumpfun::pda;
+
+        let mint = Pubkey::new_unique();
+        assert_eq!(
+            pda::find_bonding_curve(&mint),
+            Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &pumpfun::ID)
+        );
+
+        // Account data fields are taken as parameters
+        let creator = Pubkey::new_unique();
+        assert_eq!(
+            pda::find_buy_creator_vault(&creator),
+            Pubkey::find_program_address(&[b"creator-vault", creator.as_ref()], &pumpfun::ID)
+        );
+    }
+
+    #[test]
+    fn test_pda_foreign_program() {
+        use pumpfun::pda;
+
+        // Associated token account of the bonding curve, with the token
+        // program and associated token program resolved from the IDL
+        let mint = Pubkey::new_unique();
+        let (bonding_curve, _) = pda::find_bonding_curve(&mint);
+        let token_program: Pubkey = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+            .parse()
+            .unwrap();
+        let ata_program: Pubkey = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            pda::find_create_associated_bonding_curve(&bonding_curve, &mint),
+            Pubkey::find_program_address(
+                &[
+                    bonding_curve.as_ref(),
+                    token_program.as_ref(),
+                    mint.as_ref()
+                ],
+                &ata_program
+            )
+        );
+    }
+
+    // ── Partial accounts ────────────────────────────────────────────
+
+    #[test]
+    fn test_buy_accounts_partial_resolve() {
+        use pumpfun::{instructions::BuyAccountsPartial, pda};
+
+        let mint = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let token_program: Pubkey = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+            .parse()
+            .unwrap();
+        let creator_vault = pda::find_buy_creator_vault(&Pubkey::new_unique()).0;
+        let partial = BuyAccountsPartial {
+            fee_recipient: Some(Pubkey::new_unique()),
+            mint: Some(mint),
+            associated_user: Some(Pubkey::new_unique()),
+            user: Some(user),
+            token_program: Some(token_program),
+            creator_vault: Some(creator_vault),
+            ..Default::default()
+        };
+
+        let accounts = partial.resolve().unwrap();
+        let (bonding_curve, _) = pda::find_bonding_curve(&mint);
+        assert_eq!(accounts.global, pda::find_global().0);
+        assert_eq!(accounts.bonding_curve, bonding_curve);
+        assert_eq!(
+            accounts.associated_bonding_curve,
+            pda::find_buy_associated_bonding_curve(&bonding_curve, &token_program, &mint).0
+        );
+        assert_eq!(
+            accounts.user_volume_accumulator,
+            pda::find_user_volume_accumulator(&user).0
+        );
+        assert_eq!(accounts.event_authority, pumpfun::events::event_authority());
+        assert_eq!(accounts.program, pumpfun::ID);
+        assert_eq!(accounts.system_program, Pubkey::default());
+        assert_eq!(accounts.creator_vault, creator_vault);
+
+        // Accounts set by the caller are kept
+        let global = Pubkey::new_unique();
+        let accounts = BuyAccountsPartial {
+            global: Some(global),
+            ..partial
+        }
+        .resolve()
+        .unwrap();
+        assert_eq!(accounts.global, global);
+    }
+
+    #[test]
+    fn test_accounts_partial_missing_account() {
+        use pumpfun::instructions::BuyAccountsPartial;
+
+        let partial = BuyAccountsPartial {
+            fee_recipient: Some(Pubkey::new_unique()),
+            mint: Some(Pubkey::new_unique()),
+            associated_user: Some(Pubkey::new_unique()),
+            user: Some(Pubkey::new_unique()),
+            token_program: Some(Pubkey::new_unique()),
+            ..Default::default()
+        };
+        // `creator_vault` is seeded by the bonding curve's creator, which
+        // cannot be derived without fetching the account
+        let err = partial.resolve().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("creator_vault"));
+
+        // Accounts derived from a missing account are missing too
+        let err = BuyAccountsPartial::default().resolve().unwrap_err();
+        assert!(err.to_string().contains("fee_recipient"));
+    }
+
+    // ── Errors ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_error_code_from_number() {
+        use pumpfun::errors::ErrorCode;
+        let err = ErrorCode::try_from(6042).unwrap();
+        assert_eq!(err, ErrorCode::BuySlippageBelowMinTokensOut);
+        assert_eq!(err.code(), 6042);
+        assert_eq!(err.name(), "BuySlippageBelowMinTokensOut");
+        assert_eq!(
+            err.msg(),
+            "Slippage: Would buy less tokens than expected min_tokens_out"
+        );
+        assert_eq!(err.to_string(), err.msg());
+    }
+
+    #[test]
+    fn test_error_code_without_msg_falls_back_to_name() {
+        use pumpfun::errors::ErrorCode;
+        let err = ErrorCode::StartTimeInThePast;
+        assert_eq!(err.code(), 6031);
+        assert_eq!(err.msg(), "StartTimeInThePast");
+    }
+
+    #[test]
+    fn test_error_code_unknown_number() {
+        use pumpfun::errors::ErrorCode;
+        assert_eq!(ErrorCode::try_from(1), Err(1));
+        assert_eq!(ErrorCode::try_from(6056), Err(6056));
+    }
+
     // ── Types ───────────────────────────────────────────────────────
 
     #[test]
@@ -225,6 +225,29 @@ mod tests {
         assert_eq!(ext.pool_id, Pubkey::default());
     }
 
+    #[test]
+    fn test_observation_state_read_fields() {
+        use raydium_clmm::accounts::ObservationState;
+
+        let struct_size = std::mem::size_of::<ObservationState>();
+        let mut data = Vec::with_capacity(8 + struct_size);
+        data.extend_from_slice(&ObservationState::DISCRIMINATOR);
+        data.extend_from_slice(&vec![0u8; struct_size]);
+        let pool_id = Pubkey::new_unique();
+        let offset = ObservationState::POOL_ID_OFFSET;
+        data[offset..offset + 32].copy_from_slice(pool_id.as_ref());
+
+        assert_eq!(ObservationState::read_pool_id(&data).unwrap(), pool_id);
+        assert!(!ObservationState::read_initialized(&data).unwrap());
+        data[ObservationState::INITIALIZED_OFFSET] = 1;
+        assert!(ObservationState::read_initialized(&data).unwrap());
+
+        // Only 0 and 1 are valid `bool`s
+        data[ObservationState::INITIALIZED_OFFSET] = 2;
+        let err = ObservationState::read_initialized(&data).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
     // ── Account enum (utils) ────────────────────────────────────────
 
     #[test]
@@ -409,7 +432,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = PoolCreatedEvent::from_logs(&[log_line.as_str()]);
+        let events = PoolCreatedEvent::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].tick_spacing, 10);
         assert_eq!(events[0].pool_state, evt.pool_state);
@@ -437,7 +460,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = CollectPersonalFeeEvent::from_logs(&[log_line.as_str()]);
+        let events = CollectPersonalFeeEvent::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].amount_0, 100_000);
         assert_eq!(events[0].amount_1, 200_000);
@@ -474,7 +497,7 @@ mod tests {
 
         // Build bs58-encoded inner instruction data:
         // [8-byte CPI event tag][8-byte discriminator][borsh payload]
-        let mut raw = vec![0u8; 8]; // CPI event tag (arbitrary for test)
+        let mut raw = anchor_parser::event::EVENT_IX_TAG.to_vec();
         raw.extend_from_slice(&SwapEvent::DISCRIMINATOR);
         evt.serialize(&mut raw).unwrap();
 
@@ -499,7 +522,7 @@ mod tests {
             amount_1: 500,
         };
 
-        let mut raw = vec![0u8; 8];
+        let mut raw = anchor_parser::event::EVENT_IX_TAG.to_vec();
         raw.extend_from_slice(&CollectPersonalFeeEvent::DISCRIMINATOR);
         fee.serialize(&mut raw).unwrap();
 
@@ -510,6 +533,62 @@ mod tests {
         assert!(events.is_empty());
     }
 
+    #[test]
+    fn test_from_cpi_logs_rejects_wrong_event_tag() {
+        use borsh::BorshSerialize;
+        use raydium_clmm::events::SwapEvent;
+        use raydium_clmm::utils::Event;
+
+        // Bytes 8..16 match the discriminator, but the tag is not Anchor's
+        let mut raw = vec![0u8; 8];
+        raw.extend_from_slice(&SwapEvent::DISCRIMINATOR);
+        SwapEvent::default().serialize(&mut raw).unwrap();
+        let bs58_data = bs58::encode(&raw).into_string();
+
+        assert!(SwapEvent::from_cpi_logs([bs58_data.as_str()]).is_empty());
+        assert!(Event::from_cpi_logs([bs58_data.as_str()]).is_empty());
+    }
+
+    #[test]
+    fn test_swap_event_from_cpi_instruction() {
+        use borsh::BorshSerialize;
+        use raydium_clmm::events::{self, SwapEvent};
+        use raydium_clmm::utils::Event;
+
+        let evt = SwapEvent {
+            amount_0: 7,
+            ..Default::default()
+        };
+        let mut data = anchor_parser::event::EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(&SwapEvent::DISCRIMINATOR);
+        evt.serialize(&mut data).unwrap();
+
+        let authority = events::event_authority();
+        assert_eq!(
+            authority,
+            Pubkey::find_program_address(&[b"__event_authority"], &raydium_clmm::ID).0
+        );
+
+        let decoded = SwapEvent::from_cpi_instruction(&raydium_clmm::ID, &[authority], &data);
+        assert_eq!(decoded.unwrap().amount_0, 7);
+        assert!(matches!(
+            Event::from_cpi_instruction(&raydium_clmm::ID, &[authority], &data),
+            Some(Event::SwapEvent(_))
+        ));
+
+        // Wrong program
+        let other = Pubkey::new_unique();
+        assert!(SwapEvent::from_cpi_instruction(&other, &[authority], &data).is_none());
+        // Not signed by the event authority
+        assert!(SwapEvent::from_cpi_instruction(&raydium_clmm::ID, &[other], &data).is_none());
+        assert!(SwapEvent::from_cpi_instruction(&raydium_clmm::ID, &[], &data).is_none());
+        assert!(Event::from_cpi_instruction(&raydium_clmm::ID, &[other], &data).is_none());
+        // Missing event tag
+        assert!(
+            SwapEvent::from_cpi_instruction(&raydium_clmm::ID, &[authority], &data[8..]).is_none()
+        );
+    }
+
     #[test]
     fn test_from_cpi_logs_empty() {
         use raydium_clmm::events::SwapEvent;
@@ -544,7 +623,7 @@ mod tests {
         let encoded = base64::engine::general_purpose::STANDARD.encode(&event_data);
         let log_line = format!("Program data: {encoded}");
 
-        let events = Event::from_logs(&[log_line.as_str()]);
+        let events = Event::from_logs([log_line.as_str()]);
         assert_eq!(events.len(), 1);
         match &events[0] {
             Event::PoolCreatedEvent(e) => {
@@ -577,7 +656,7 @@ mod tests {
         };
 
         // Build bs58-encoded inner instruction data
-        let mut raw = vec![0u8; 8]; // CPI event tag
+        let mut raw = anchor_parser::event::EVENT_IX_TAG.to_vec();
         raw.extend_from_slice(&SwapEvent::DISCRIMINATOR);
         evt.serialize(&mut raw).unwrap();
 
@@ -1480,7 +1559,7 @@ mod tests {
                 system_program,
                 rent,
             },
-            param.clone(),
+            param,
         );
 
         let expected_ix = solana_sdk::instruction::Instruction::new_with_bytes(
@@ -2277,6 +2356,105 @@ mod tests {
         assert_ix(&gen_ix, &expected_ix);
     }
 
+    // ── PDAs ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pda_arg_seeds() {
+        use raydium_clmm::pda;
+
+        assert_eq!(
+            pda::find_amm_config(4),
+            Pubkey::find_program_address(&[b"amm_config", &4u16.to_le_bytes()], &raydium_clmm::ID)
+        );
+
+        let pool_state = Pubkey::new_unique();
+        assert_eq!(
+            pda::find_tick_array_lower(&pool_state, -120),
+            Pubkey::find_program_address(
+                &[b"tick_array", pool_state.as_ref(), &(-120i32).to_le_bytes()],
+                &raydium_clmm::ID
+            )
+        );
+    }
+
+    #[test]
+    fn test_pda_variants_per_instruction() {
+        use raydium_clmm::pda;
+
+        // `protocol_position` is derived from instruction args in
+        // `open_position` and from the personal position's fields in
+        // `decrease_liquidity`; both yield the same address
+        let pool_state = Pubkey::new_unique();
+        assert_eq!(
+            pda::find_open_position_protocol_position(&pool_state, -10, 10),
+            pda::find_decrease_liquidity_protocol_position(&pool_state, -10, 10)
+        );
+    }
+
+    // ── Partial accounts ────────────────────────────────────────────
+
+    #[test]
+    fn test_accounts_partial_resolves_arg_seeds() {
+        use raydium_clmm::instructions::{OpenPositionAccountsPartial, OpenPositionArgs};
+        use raydium_clmm::pda;
+
+        let pool_state = Pubkey::new_unique();
+        let position_nft_mint = Pubkey::new_unique();
+        let partial = OpenPositionAccountsPartial {
+            payer: Some(Pubkey::new_unique()),
+            position_nft_owner: Some(Pubkey::new_unique()),
+            position_nft_mint: Some(position_nft_mint),
+            metadata_account: Some(Pubkey::new_unique()),
+            pool_state: Some(pool_state),
+            token_account_0: Some(Pubkey::new_unique()),
+            token_account_1: Some(Pubkey::new_unique()),
+            token_vault_0: Some(Pubkey::new_unique()),
+            token_vault_1: Some(Pubkey::new_unique()),
+            ..Default::default()
+        };
+        let args = OpenPositionArgs {
+            tick_lower_index: -60,
+            tick_upper_index: 60,
+            tick_array_lower_start_index: -3600,
+            tick_array_upper_start_index: 0,
+            liquidity: 1,
+            amount_0_max: 2,
+            amount_1_max: 3,
+        };
+
+        let accounts = partial.resolve(&args).unwrap();
+        assert_eq!(
+            accounts.protocol_position,
+            pda::find_open_position_protocol_position(&pool_state, -60, 60).0
+        );
+        assert_eq!(
+            accounts.tick_array_lower,
+            pda::find_tick_array_lower(&pool_state, -3600).0
+        );
+        assert_eq!(
+            accounts.tick_array_upper,
+            pda::find_tick_array_upper(&pool_state, 0).0
+        );
+        assert_eq!(
+            accounts.personal_position,
+            pda::find_personal_position(&position_nft_mint).0
+        );
+    }
+
+    // ── Errors ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_error_code_roundtrip() {
+        use raydium_clmm::errors::ErrorCode;
+        let err = ErrorCode::try_from(6021).unwrap();
+        assert_eq!(err, ErrorCode::PriceSlippageCheck);
+        assert_eq!(err.code(), 6021);
+        assert_eq!(err.to_string(), "Price slippage check");
+
+        let boxed: Box<dyn std::error::Error> = Box::new(err);
+        assert_eq!(boxed.to_string(), "Price slippage check");
+    }
+
     // ── Types ───────────────────────────────────────────────────────
 
     #[test]
@@ -2301,4 +2479,29 @@ mod tests {
         assert_eq!(param.open_time, 1700000000);
         assert_eq!(param.end_time, 1700100000);
     }
+
+    // ── Serde ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_serde_packed_account_with_long_arrays() {
+        use raydium_clmm::accounts::TickArrayState;
+
+        let mut state: TickArrayState = bytemuck::Zeroable::zeroed();
+        state.pool_id = Pubkey::new_unique();
+        state.start_tick_index = -600;
+        state.ticks[59].liquidity_net = i128::MIN;
+        state.padding[106] = 7;
+
+        let value = serde_json::to_value(state).unwrap();
+        assert_eq!(value["pool_id"], state.pool_id.to_string());
+        assert_eq!(value["ticks"].as_array().unwrap().len(), 60);
+        assert_eq!(
+            value["ticks"][59]["liquidity_net"],
+            "-170141183460469231731687303715884105728"
+        );
+        assert_eq!(value["padding"][106], 7);
+
+        let parsed: TickArrayState = serde_json::from_value(value).unwrap();
+        assert_eq!(bytemuck::bytes_of(&parsed), bytemuck::bytes_of(&state));
+    }
 }
@@ -0,0 +1,267 @@
+use anchor_parser::declare_program;
+
+// Synthetic IDL covering edge cases that the real-world IDLs don't hit.
+declare_program!(vault);
+
+#[cfg(test)]
+mod tests {
+    use super::vault;
+    use solana_sdk::pubkey::Pubkey;
+
+    // ── Instructions ────────────────────────────────────────────────
+
+    #[test]
+    fn test_args_struct_name_avoids_idl_type() {
+        use borsh::BorshSerialize;
+        use vault::instructions::{self, InitializeIxArgs, Instruction};
+        use vault::types::InitializeArgs;
+
+        let args = InitializeArgs {
+            capacity: 100,
+            label: "main".to_string(),
+        };
+        let ix = instructions::initialize(
+            &vault::ID,
+            &instructions::InitializeAccounts {
+                vault: Pubkey::new_unique(),
+                authority: Pubkey::new_unique(),
+                system_program: Pubkey::default(),
+            },
+            args.clone(),
+        );
+
+        let mut expected = InitializeIxArgs::DISCRIMINATOR.to_vec();
+        args.serialize(&mut expected).unwrap();
+        assert_eq!(ix.data, expected);
+
+        match Instruction::decode(&ix.data).unwrap() {
+            Instruction::Initialize(InitializeIxArgs { args }) => {
+                assert_eq!(args.capacity, 100);
+                assert_eq!(args.label, "main");
+            }
+            other => panic!("unexpected instruction: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_instruction_without_accounts() {
+        use vault::instructions::{self, Instruction, InstructionAccounts, PingAccounts};
+
+        let ix = instructions::ping(&vault::ID, 7);
+        assert!(ix.accounts.is_empty());
+        assert_eq!(PingAccounts::LEN, 0);
+        assert!(PingAccounts::from_account_metas(&vault::ID, &[]).is_ok());
+
+        let decoded = Instruction::decode(&ix.data).unwrap();
+        assert!(matches!(decoded, Instruction::Ping(ref args) if args.nonce == 7));
+        assert!(matches!(
+            InstructionAccounts::from_account_keys(&decoded, &vault::ID, &[], &[]),
+            Ok(InstructionAccounts::Ping(PingAccounts))
+        ));
+    }
+
+    // ── PDAs ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pda_names_in_composites_are_unique() {
+        use vault::pda;
+
+        let authority = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        // `find_transfer_vault` belongs to the `transfer_vault` account, so
+        // the two `vault` PDAs of `transfer` are named after their composite
+        assert_eq!(
+            pda::find_transfer_vault().0,
+            Pubkey::find_program_address(&[b"transfer"], &vault::ID).0
+        );
+        assert_eq!(
+            pda::find_transfer_from_vault(&authority).0,
+            Pubkey::find_program_address(&[b"vault", authority.as_ref()], &vault::ID).0
+        );
+        assert_eq!(
+            pda::find_transfer_to_vault(&owner).0,
+            Pubkey::find_program_address(&[b"vault", owner.as_ref()], &vault::ID).0
+        );
+    }
+
+    // ── Partial accounts ────────────────────────────────────────────
+
+    #[test]
+    fn test_partial_resolves_pdas_within_composites() {
+        use vault::instructions::TransferAccountsPartial;
+
+        // The top-level `authority` shares its name with the `from` group's
+        let authority = Pubkey::new_unique();
+        let from_authority = Pubkey::new_unique();
+        let to_owner = Pubkey::new_unique();
+        let accounts = TransferAccountsPartial {
+            authority: Some(authority),
+            from_authority: Some(from_authority),
+            to_owner: Some(to_owner),
+            ..Default::default()
+        }
+        .resolve()
+        .unwrap();
+
+        assert_eq!(
+            accounts.from_vault,
+            vault::pda::find_transfer_from_vault(&from_authority).0
+        );
+        assert_eq!(
+            accounts.to_vault,
+            vault::pda::find_transfer_to_vault(&to_owner).0
+        );
+    }
+
+    #[test]
+    fn test_partial_relations_within_composites() {
+        use vault::accounts::Vault;
+        use vault::instructions::WithdrawAccountsPartial;
+
+        let vault_data = |authority| Vault {
+            authority,
+            capacity: 10,
+            bump: 255,
+        };
+        let from_authority = Pubkey::new_unique();
+        let to_authority = Pubkey::new_unique();
+
+        // `from.vault`'s type is given by the `receipt` seed, so it takes the
+        // account itself; `to.vault`'s is not, so it goes through
+        // `AccountFields`. Neither is tied to the top-level `vault`.
+        let partial = WithdrawAccountsPartial {
+            vault: Some(Pubkey::new_unique()),
+            from_vault: Some(Pubkey::new_unique()),
+            to_vault: Some(Pubkey::new_unique()),
+            from_receipt: Some(Pubkey::new_unique()),
+            ..Default::default()
+        }
+        .with_from_vault_data(&vault_data(from_authority))
+        .with_to_vault_data(&vault::utils::Account::Vault(vault_data(to_authority)));
+        assert_eq!(partial.from_authority, Some(from_authority));
+        assert_eq!(partial.to_authority, Some(to_authority));
+
+        let accounts = partial.resolve().unwrap();
+        assert_eq!(accounts.from_authority, from_authority);
+        assert_eq!(accounts.to_authority, to_authority);
+    }
+
+    // ── Events ──────────────────────────────────────────────────────
+
+    fn program_data(discriminator: &[u8], event: &impl borsh::BorshSerialize) -> String {
+        use base64::Engine;
+
+        let mut data = discriminator.to_vec();
+        event.serialize(&mut data).unwrap();
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(data)
+        )
+    }
+
+    #[test]
+    fn test_iter_logs_long_discriminator() {
+        use vault::events::{Deposited, Withdrawn};
+        use vault::utils::Event;
+
+        // 16-byte custom discriminators sharing their first 12 bytes
+        assert_eq!(Deposited::DISCRIMINATOR.len(), 16);
+        assert_eq!(
+            Deposited::DISCRIMINATOR[..12],
+            Withdrawn::DISCRIMINATOR[..12]
+        );
+
+        let vault = Pubkey::new_unique();
+        let logs = [
+            program_data(&Deposited::DISCRIMINATOR, &Deposited { vault, amount: 5 }),
+            program_data(&Withdrawn::DISCRIMINATOR, &Withdrawn { vault, amount: 3 }),
+        ];
+
+        let deposits: Vec<_> = Deposited::iter_logs(&logs).collect();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].amount, 5);
+        assert_eq!(Deposited::from_logs(&logs).len(), 1);
+
+        let events: Vec<_> = Event::iter_logs(&logs).collect();
+        assert!(matches!(
+            events.as_slice(),
+            [Event::Deposited(d), Event::Withdrawn(w)] if d.amount == 5 && w.amount == 3
+        ));
+    }
+
+    // ── Accounts ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_serialized_len_rejects_nan() {
+        use vault::accounts::Oracle;
+
+        let oracle = Oracle {
+            feed: [0; 160],
+            price: 1.5,
+        };
+        assert_eq!(oracle.serialized_len().unwrap(), 8 + 160 + 8);
+
+        // Borsh refuses to serialize NaN floats
+        let oracle = Oracle {
+            feed: [0; 160],
+            price: f64::NAN,
+        };
+        assert!(oracle.serialized_len().is_err());
+        assert!(oracle.to_account_data().is_err());
+    }
+
+    #[test]
+    fn test_filter_large_byte_array() {
+        use solana_client::rpc_filter::RpcFilterType;
+        use vault::accounts::Oracle;
+
+        let oracle = Oracle {
+            feed: [7; 160],
+            price: 1.5,
+        };
+        let data = oracle.to_account_data().unwrap();
+
+        // Longer than the 128 bytes RPC nodes accept as base58
+        let RpcFilterType::Memcmp(memcmp) = Oracle::filter_feed(&oracle.feed) else {
+            panic!("expected a memcmp filter");
+        };
+        assert_eq!(memcmp.offset(), Oracle::FEED_OFFSET);
+        assert!(matches!(
+            serde_json::to_value(&memcmp).unwrap()["encoding"].as_str(),
+            Some("base64")
+        ));
+        assert!(memcmp.bytes_match(&data));
+        assert!(!memcmp.bytes_match(&[0; 8 + 160 + 8]));
+    }
+
+    // ── Serde ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_serde_alias_fields() {
+        use serde_json::json;
+        use vault::types::Receipt;
+
+        // `Amount = u64`, `Owner = Pubkey` and `Sig = [u8; 64]` keep the
+        // representation of the aliased type
+        let receipt = Receipt {
+            amount: u64::MAX,
+            owner: Pubkey::new_unique(),
+            sig: [7; 64],
+        };
+
+        let value = serde_json::to_value(receipt).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "amount": "18446744073709551615",
+                "owner": receipt.owner.to_string(),
+                "sig": receipt.sig.to_vec(),
+            })
+        );
+
+        let parsed: Receipt = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.amount, u64::MAX);
+        assert_eq!(parsed.owner, receipt.owner);
+        assert_eq!(parsed.sig, [7; 64]);
+    }
+}
@@ -0,0 +1,445 @@
+//! Legacy (pre-0.30) Anchor IDL format, converted to the current spec.
+//!
+//! Legacy IDLs have no discriminators, no top-level `address`, camelCase
+//! names, `isMut` / `isSigner` account flags, and `publicKey` /
+//! `{"defined": "Name"}` types. Account and event layouts are declared inline
+//! rather than in `types`.
+
+use heck::ToSnakeCase;
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::{
+    Idl, IdlAccount, IdlArrayLen, IdlConst, IdlDefinedFields, IdlEnumVariant, IdlErrorCode,
+    IdlEvent, IdlField, IdlInstruction, IdlInstructionAccount, IdlInstructionAccountItem,
+    IdlInstructionAccounts, IdlMetadata, IdlSerialization, IdlType, IdlTypeDef, IdlTypeDefTy,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyIdl {
+    pub version: String,
+    pub name: String,
+    #[serde(default)]
+    pub docs: Vec<String>,
+    #[serde(default)]
+    pub constants: Vec<LegacyConst>,
+    pub instructions: Vec<LegacyInstruction>,
+    #[serde(default)]
+    pub accounts: Vec<LegacyTypeDef>,
+    #[serde(default)]
+    pub types: Vec<LegacyTypeDef>,
+    #[serde(default)]
+    pub events: Vec<LegacyEvent>,
+    #[serde(default)]
+    pub errors: Vec<IdlErrorCode>,
+    #[serde(default)]
+    pub metadata: Option<LegacyMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyMetadata {
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyConst {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Value,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyInstruction {
+    pub name: String,
+    #[serde(default)]
+    pub docs: Vec<String>,
+    pub accounts: Vec<LegacyAccountItem>,
+    pub args: Vec<LegacyField>,
+    #[serde(default)]
+    pub returns: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LegacyAccountItem {
+    Composite(LegacyAccounts),
+    Single(LegacyAccount),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyAccount {
+    pub name: String,
+    #[serde(default)]
+    pub docs: Vec<String>,
+    pub is_mut: bool,
+    pub is_signer: bool,
+    #[serde(default)]
+    pub is_optional: bool,
+    #[serde(default)]
+    pub relations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyAccounts {
+    pub name: String,
+    pub accounts: Vec<LegacyAccountItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyField {
+    pub name: String,
+    #[serde(default)]
+    pub docs: Vec<String>,
+    #[serde(rename = "type")]
+    pub ty: Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyTypeDef {
+    pub name: String,
+    #[serde(default)]
+    pub docs: Vec<String>,
+    #[serde(rename = "type")]
+    pub ty: LegacyTypeDefTy,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum LegacyTypeDefTy {
+    Struct {
+        #[serde(default)]
+        fields: Vec<LegacyField>,
+    },
+    Enum {
+        variants: Vec<LegacyEnumVariant>,
+    },
+    Alias {
+        value: Value,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyEnumVariant {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Option<LegacyEnumFields>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LegacyEnumFields {
+    Named(Vec<LegacyField>),
+    Tuple(Vec<Value>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LegacyEvent {
+    pub name: String,
+    pub fields: Vec<LegacyField>,
+}
+
+/// Whether `json` is a legacy IDL: the current spec requires a top-level
+/// `address`, which legacy IDLs keep under `metadata` if at all.
+pub fn is_legacy(json: &Value) -> bool {
+    json.get("address").is_none()
+}
+
+/// First 8 bytes of `sha256("<namespace>:<name>")`, as computed by Anchor.
+fn discriminator(namespace: &str, name: &str) -> Vec<u8> {
+    Sha256::digest(format!("{namespace}:{name}").as_bytes())[..8].to_vec()
+}
+
+impl LegacyIdl {
+    /// Convert to the current spec.
+    ///
+    /// Instruction, account and field names are converted to snake_case,
+    /// as `anchor idl convert` does. The program address is left empty when
+    /// `metadata.address` is absent.
+    pub fn convert(self) -> Result<Idl, String> {
+        let mut types = Vec::new();
+
+        let accounts = self
+            .accounts
+            .into_iter()
+            .map(|acc| {
+                let account = IdlAccount {
+                    name: acc.name.clone(),
+                    discriminator: discriminator("account", &acc.name),
+                };
+                types.push(convert_type_def(acc)?);
+                Ok(account)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let events = self
+            .events
+            .into_iter()
+            .map(|ev| {
+                let event = IdlEvent {
+                    name: ev.name.clone(),
+                    discriminator: discriminator("event", &ev.name),
+                };
+                types.push(IdlTypeDef {
+                    name: ev.name,
+                    docs: Vec::new(),
+                    serialization: IdlSerialization::Borsh,
+                    repr: None,
+                    generics: Vec::new(),
+                    ty: IdlTypeDefTy::Struct {
+                        fields: convert_named_fields(ev.fields)?,
+                    },
+                });
+                Ok(event)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        for ty in self.types {
+            types.push(convert_type_def(ty)?);
+        }
+
+        let instructions = self
+            .instructions
+            .into_iter()
+            .map(|ix| {
+                let name = ix.name.to_snake_case();
+                Ok(IdlInstruction {
+                    discriminator: discriminator("global", &name),
+                    name,
+                    docs: ix.docs,
+                    accounts: convert_instruction_accounts(ix.accounts),
+                    args: ix
+                        .args
+                        .into_iter()
+                        .map(convert_field)
+                        .collect::<Result<_, _>>()?,
+                    returns: ix.returns.as_ref().map(convert_type).transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let constants = self
+            .constants
+            .into_iter()
+            .map(|c| {
+                Ok(IdlConst {
+                    name: c.name,
+                    docs: Vec::new(),
+                    ty: convert_type(&c.ty)?,
+                    value: c.value,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Idl {
+            address: self
+                .metadata
+                .and_then(|metadata| metadata.address)
+                .unwrap_or_default(),
+            metadata: IdlMetadata {
+                name: self.name,
+                version: self.version,
+                spec: "legacy".to_string(),
+                description: None,
+            },
+            docs: self.docs,
+            instructions,
+            accounts,
+            events,
+            errors: self.errors,
+            types,
+            constants,
+        })
+    }
+}
+
+/// Convert instruction accounts, inverting `relations`.
+///
+/// A legacy account lists the accounts whose address it stores
+/// (`has_one`), while the current spec lists them on the stored account.
+fn convert_instruction_accounts(items: Vec<LegacyAccountItem>) -> Vec<IdlInstructionAccountItem> {
+    let mut relations = Vec::new();
+    collect_relations(&items, &mut relations);
+    let mut items = convert_account_items(items);
+    add_relations(&mut items, &relations);
+    items
+}
+
+/// `(stored account, account storing it)` pairs, snake_cased.
+fn collect_relations(items: &[LegacyAccountItem], relations: &mut Vec<(String, String)>) {
+    for item in items {
+        match item {
+            LegacyAccountItem::Single(acc) => relations.extend(
+                acc.relations
+                    .iter()
+                    .map(|related| (related.to_snake_case(), acc.name.to_snake_case())),
+            ),
+            LegacyAccountItem::Composite(comp) => collect_relations(&comp.accounts, relations),
+        }
+    }
+}
+
+fn add_relations(items: &mut [IdlInstructionAccountItem], relations: &[(String, String)]) {
+    for item in items {
+        match item {
+            IdlInstructionAccountItem::Single(acc) => acc.relations.extend(
+                relations
+                    .iter()
+                    .filter(|(related, _)| *related == acc.name)
+                    .map(|(_, source)| source.clone()),
+            ),
+            IdlInstructionAccountItem::Composite(comp) => {
+                add_relations(&mut comp.accounts, relations)
+            }
+        }
+    }
+}
+
+fn convert_account_items(items: Vec<LegacyAccountItem>) -> Vec<IdlInstructionAccountItem> {
+    items
+        .into_iter()
+        .map(|item| match item {
+            LegacyAccountItem::Single(acc) => {
+                IdlInstructionAccountItem::Single(IdlInstructionAccount {
+                    name: acc.name.to_snake_case(),
+                    docs: acc.docs,
+                    writable: acc.is_mut,
+                    signer: acc.is_signer,
+                    optional: acc.is_optional,
+                    address: None,
+                    pda: None,
+                    relations: Vec::new(),
+                })
+            }
+            LegacyAccountItem::Composite(comp) => {
+                IdlInstructionAccountItem::Composite(IdlInstructionAccounts {
+                    name: comp.name.to_snake_case(),
+                    accounts: convert_account_items(comp.accounts),
+                })
+            }
+        })
+        .collect()
+}
+
+fn convert_type_def(ty: LegacyTypeDef) -> Result<IdlTypeDef, String> {
+    let converted = match ty.ty {
+        LegacyTypeDefTy::Struct { fields } => IdlTypeDefTy::Struct {
+            fields: convert_named_fields(fields)?,
+        },
+        LegacyTypeDefTy::Enum { variants } => IdlTypeDefTy::Enum {
+            variants: variants
+                .into_iter()
+                .map(|variant| {
+                    let fields = match variant.fields {
+                        None => None,
+                        Some(LegacyEnumFields::Named(fields)) => convert_named_fields(fields)?,
+                        Some(LegacyEnumFields::Tuple(tys)) => Some(IdlDefinedFields::Tuple(
+                            tys.iter().map(convert_type).collect::<Result<_, _>>()?,
+                        )),
+                    };
+                    Ok(IdlEnumVariant {
+                        name: variant.name,
+                        fields,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+        },
+        LegacyTypeDefTy::Alias { value } => IdlTypeDefTy::Type {
+            alias: convert_type(&value)?,
+        },
+    };
+    Ok(IdlTypeDef {
+        name: ty.name,
+        docs: ty.docs,
+        serialization: IdlSerialization::Borsh,
+        repr: None,
+        generics: Vec::new(),
+        ty: converted,
+    })
+}
+
+fn convert_named_fields(fields: Vec<LegacyField>) -> Result<Option<IdlDefinedFields>, String> {
+    if fields.is_empty() {
+        return Ok(None);
+    }
+    let fields = fields
+        .into_iter()
+        .map(convert_field)
+        .collect::<Result<_, _>>()?;
+    Ok(Some(IdlDefinedFields::Named(fields)))
+}
+
+fn convert_field(field: LegacyField) -> Result<IdlField, String> {
+    Ok(IdlField {
+        ty: convert_type(&field.ty)?,
+        name: field.name.to_snake_case(),
+        docs: field.docs,
+    })
+}
+
+/// Convert a legacy type, e.g. `"publicKey"` or `{"defined": "Name"}`.
+fn convert_type(ty: &Value) -> Result<IdlType, String> {
+    let unsupported = || format!("Unsupported legacy IDL type: {ty}");
+
+    if let Some(name) = ty.as_str() {
+        return Ok(match name {
+            "bool" => IdlType::Bool,
+            "u8" => IdlType::U8,
+            "i8" => IdlType::I8,
+            "u16" => IdlType::U16,
+            "i16" => IdlType::I16,
+            "u32" => IdlType::U32,
+            "i32" => IdlType::I32,
+            "f32" => IdlType::F32,
+            "u64" => IdlType::U64,
+            "i64" => IdlType::I64,
+            "f64" => IdlType::F64,
+            "u128" => IdlType::U128,
+            "i128" => IdlType::I128,
+            "u256" => IdlType::U256,
+            "i256" => IdlType::I256,
+            "bytes" => IdlType::Bytes,
+            "string" => IdlType::String,
+            "publicKey" => IdlType::Pubkey,
+            _ => return Err(unsupported()),
+        });
+    }
+
+    let (kind, inner) = ty
+        .as_object()
+        .filter(|obj| obj.len() == 1)
+        .and_then(|obj| obj.iter().next())
+        .ok_or_else(unsupported)?;
+    match kind.as_str() {
+        "defined" => Ok(IdlType::Defined {
+            name: inner.as_str().ok_or_else(unsupported)?.to_string(),
+            generics: Vec::new(),
+        }),
+        "option" => Ok(IdlType::Option(Box::new(convert_type(inner)?))),
+        "vec" => Ok(IdlType::Vec(Box::new(convert_type(inner)?))),
+        "array" => {
+            let [elem, len] = inner
+                .as_array()
+                .map(Vec::as_slice)
+                .ok_or_else(unsupported)?
+            else {
+                return Err(unsupported());
+            };
+            let len = match len {
+                Value::Number(n) => {
+                    IdlArrayLen::Value(n.as_u64().ok_or_else(unsupported)? as usize)
+                }
+                Value::String(generic) => IdlArrayLen::Generic(generic.clone()),
+                _ => return Err(unsupported()),
+            };
+            Ok(IdlType::Array(Box::new(convert_type(elem)?), len))
+        }
+        "generic" => Ok(IdlType::Generic(
+            inner.as_str().ok_or_else(unsupported)?.to_string(),
+        )),
+        _ => Err(unsupported()),
+    }
+}
@@ -1,8 +1,15 @@
+//! Anchor IDL model shared by `anchor-parser` and `anchor-parser-macros`.
+//!
+//! Mirrors the Anchor IDL spec 0.1.0 JSON format. Legacy (pre-0.30) IDLs are
+//! converted by [`Idl::from_json`].
+
+pub mod legacy;
+pub mod select;
+
 use serde::Deserialize;
 
 /// Top-level IDL structure (Anchor IDL spec 0.1.0).
 #[derive(Debug, Clone, Deserialize)]
-#[allow(dead_code)]
 pub struct Idl {
     pub address: String,
     pub metadata: IdlMetadata,
@@ -21,8 +28,20 @@ pub struct Idl {
     pub constants: Vec<IdlConst>,
 }
 
+impl Idl {
+    /// Parse an IDL, converting the legacy (pre-0.30) format if detected.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if legacy::is_legacy(&value) {
+            let idl: legacy::LegacyIdl = serde_json::from_value(value)?;
+            idl.convert().map_err(serde::de::Error::custom)
+        } else {
+            serde_json::from_value(value)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
-#[allow(dead_code)]
 pub struct IdlMetadata {
     pub name: String,
     pub version: String,
@@ -34,7 +53,6 @@ pub struct IdlMetadata {
 // ── Instructions ─────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
-#[allow(dead_code)]
 pub struct IdlInstruction {
     pub name: String,
     #[serde(default)]
@@ -66,6 +84,11 @@ pub struct IdlInstructionAccount {
     pub optional: bool,
     #[serde(default)]
     pub address: Option<String>,
+    #[serde(default)]
+    pub pda: Option<IdlPda>,
+    /// Accounts storing this account's address in a field of the same name.
+    #[serde(default)]
+    pub relations: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -74,6 +97,44 @@ pub struct IdlInstructionAccounts {
     pub accounts: Vec<IdlInstructionAccountItem>,
 }
 
+// ── PDAs ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct IdlPda {
+    pub seeds: Vec<IdlSeed>,
+    /// Program the address is derived under; the IDL's own program if absent.
+    #[serde(default)]
+    pub program: Option<IdlSeed>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlSeed {
+    Const(IdlSeedConst),
+    Arg(IdlSeedArg),
+    Account(IdlSeedAccount),
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct IdlSeedConst {
+    pub value: Vec<u8>,
+}
+
+/// Instruction argument, or a field of one (`arg.field`).
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct IdlSeedArg {
+    pub path: String,
+}
+
+/// Instruction account, or a field of its data (`account.field`) when
+/// `account` names the account type.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct IdlSeedAccount {
+    pub path: String,
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
 // ── Accounts & Events (metadata only) ───────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
@@ -91,7 +152,6 @@ pub struct IdlEvent {
 // ── Errors ───────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
-#[allow(dead_code)]
 pub struct IdlErrorCode {
     pub code: u32,
     pub name: String,
@@ -102,7 +162,6 @@ pub struct IdlErrorCode {
 // ── Constants ────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
-#[allow(dead_code)]
 pub struct IdlConst {
     pub name: String,
     #[serde(default)]
@@ -0,0 +1,179 @@
+//! Restricting an IDL to a subset of its items.
+
+use super::{
+    Idl, IdlDefinedFields, IdlGenericArg, IdlInstructionAccountItem, IdlSeed, IdlType, IdlTypeDefTy,
+};
+
+/// Items of one kind to keep.
+#[derive(Debug, Clone)]
+pub enum Group {
+    All,
+    Named(Vec<String>),
+}
+
+/// Items to generate, as given by `only(...)`. Kinds left as `None` are
+/// dropped entirely.
+///
+/// `types` lists type definitions to keep in addition to the ones the
+/// selected items reference.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    pub types: Option<Group>,
+    pub accounts: Option<Group>,
+    pub events: Option<Group>,
+    pub instructions: Option<Group>,
+    pub constants: Option<Group>,
+    pub errors: Option<Group>,
+}
+
+/// Keep the items selected by `group`, erroring on unknown names.
+fn retain<T>(
+    items: &mut Vec<T>,
+    group: &Option<Group>,
+    kind: &str,
+    name: impl Fn(&T) -> &str,
+) -> Result<(), String> {
+    match group {
+        None => items.clear(),
+        Some(Group::All) => {}
+        Some(Group::Named(names)) => {
+            if let Some(unknown) = names
+                .iter()
+                .find(|n| !items.iter().any(|item| name(item) == n.as_str()))
+            {
+                return Err(format!("Unknown {kind} `{unknown}` in `only(...)`"));
+            }
+            items.retain(|item| names.iter().any(|n| n == name(item)));
+        }
+    }
+    Ok(())
+}
+
+/// Push the names of the type definitions `ty` refers to.
+fn referenced_types(ty: &IdlType, names: &mut Vec<String>) {
+    match ty {
+        IdlType::Option(inner) | IdlType::Vec(inner) | IdlType::Array(inner, _) => {
+            referenced_types(inner, names)
+        }
+        IdlType::Defined { name, generics } => {
+            names.push(name.clone());
+            for generic in generics {
+                if let IdlGenericArg::Type { ty } = generic {
+                    referenced_types(ty, names);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn referenced_field_types(fields: &Option<IdlDefinedFields>, names: &mut Vec<String>) {
+    match fields {
+        None => {}
+        Some(IdlDefinedFields::Named(fields)) => {
+            for field in fields {
+                referenced_types(&field.ty, names);
+            }
+        }
+        Some(IdlDefinedFields::Tuple(tys)) => {
+            for ty in tys {
+                referenced_types(ty, names);
+            }
+        }
+    }
+}
+
+/// Push the account types named by `account.field` PDA seeds, whose
+/// definitions are needed to type the seed.
+fn seed_account_types(items: &[IdlInstructionAccountItem], names: &mut Vec<String>) {
+    for item in items {
+        match item {
+            IdlInstructionAccountItem::Single(acc) => {
+                let Some(pda) = &acc.pda else { continue };
+                for seed in pda.seeds.iter().chain(&pda.program) {
+                    if let IdlSeed::Account(seed) = seed
+                        && let Some(account) = &seed.account
+                    {
+                        names.push(account.clone());
+                    }
+                }
+            }
+            IdlInstructionAccountItem::Composite(comp) => seed_account_types(&comp.accounts, names),
+        }
+    }
+}
+
+impl Idl {
+    /// Drop the items not in `selection`, along with the type definitions
+    /// no remaining item references.
+    pub fn select(&mut self, selection: &Selection) -> Result<(), String> {
+        retain(&mut self.accounts, &selection.accounts, "account", |a| {
+            &a.name
+        })?;
+        retain(&mut self.events, &selection.events, "event", |e| &e.name)?;
+        retain(
+            &mut self.instructions,
+            &selection.instructions,
+            "instruction",
+            |ix| &ix.name,
+        )?;
+        retain(&mut self.constants, &selection.constants, "constant", |c| {
+            &c.name
+        })?;
+        retain(&mut self.errors, &selection.errors, "error", |e| &e.name)?;
+
+        let mut pending: Vec<String> = Vec::new();
+        match &selection.types {
+            Some(Group::All) => return Ok(()),
+            Some(Group::Named(names)) => {
+                if let Some(unknown) = names
+                    .iter()
+                    .find(|n| !self.types.iter().any(|ty| ty.name == **n))
+                {
+                    return Err(format!("Unknown type `{unknown}` in `only(...)`"));
+                }
+                pending.extend(names.iter().cloned());
+            }
+            None => {}
+        }
+
+        // Accounts and events are defined by the type of the same name
+        pending.extend(self.accounts.iter().map(|acc| acc.name.clone()));
+        pending.extend(self.events.iter().map(|ev| ev.name.clone()));
+        for ix in &self.instructions {
+            for arg in &ix.args {
+                referenced_types(&arg.ty, &mut pending);
+            }
+            if let Some(returns) = &ix.returns {
+                referenced_types(returns, &mut pending);
+            }
+            seed_account_types(&ix.accounts, &mut pending);
+        }
+        for constant in &self.constants {
+            referenced_types(&constant.ty, &mut pending);
+        }
+
+        let mut keep: Vec<String> = Vec::new();
+        while let Some(name) = pending.pop() {
+            if keep.contains(&name) {
+                continue;
+            }
+            let Some(ty_def) = self.types.iter().find(|ty| ty.name == name) else {
+                continue;
+            };
+            match &ty_def.ty {
+                IdlTypeDefTy::Struct { fields } => referenced_field_types(fields, &mut pending),
+                IdlTypeDefTy::Enum { variants } => {
+                    for variant in variants {
+                        referenced_field_types(&variant.fields, &mut pending);
+                    }
+                }
+                IdlTypeDefTy::Type { alias } => referenced_types(alias, &mut pending),
+            }
+            keep.push(name);
+        }
+        self.types.retain(|ty| keep.contains(&ty.name));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitStr, Token, parenthesized};
+
+use crate::idl::select::{Group, Selection};
+
+/// Arguments of `declare_program!`: the module name, followed by optional
+/// `key = "value"` pairs and an `only(...)` selection.
+pub struct DeclareProgramArgs {
+    pub name: Ident,
+    /// IDL file to load instead of `idls/{name}.json`.
+    pub path: Option<LitStr>,
+    /// Program ID overriding the IDL's address.
+    pub program_id: Option<LitStr>,
+    /// Items to generate; everything if absent.
+    pub only: Option<Selection>,
+}
+
+impl Parse for DeclareProgramArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let mut path = None;
+        let mut program_id = None;
+        let mut only = None;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            if key == "only" {
+                if only.replace(parse_selection(input)?).is_some() {
+                    return Err(syn::Error::new(key.span(), "Duplicate argument `only`"));
+                }
+                continue;
+            }
+            input.parse::<Token![=]>()?;
+            let value: LitStr = input.parse()?;
+            let slot = match key.to_string().as_str() {
+                "path" => &mut path,
+                "program_id" => &mut program_id,
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "Unknown argument `{key}`, expected `path`, `program_id` or `only`"
+                        ),
+                    ));
+                }
+            };
+            if slot.replace(value).is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("Duplicate argument `{key}`"),
+                ));
+            }
+        }
+
+        Ok(Self {
+            name,
+            path,
+            program_id,
+            only,
+        })
+    }
+}
+
+/// Parse `(accounts(A, B), events(C), errors)`: each kind either lists the
+/// items to keep or, when bare, keeps all of them.
+fn parse_selection(input: ParseStream) -> syn::Result<Selection> {
+    let content;
+    parenthesized!(content in input);
+
+    let mut selection = Selection::default();
+    while !content.is_empty() {
+        let kind: Ident = content.parse()?;
+        let group = if content.peek(syn::token::Paren) {
+            let names;
+            parenthesized!(names in content);
+            let names = Punctuated::<Ident, Token![,]>::parse_terminated(&names)?;
+            Group::Named(names.iter().map(Ident::to_string).collect())
+        } else {
+            Group::All
+        };
+        let slot = match kind.to_string().as_str() {
+            "types" => &mut selection.types,
+            "accounts" => &mut selection.accounts,
+            "events" => &mut selection.events,
+            "instructions" => &mut selection.instructions,
+            "constants" => &mut selection.constants,
+            "errors" => &mut selection.errors,
+            _ => {
+                return Err(syn::Error::new(
+                    kind.span(),
+                    format!(
+                        "Unknown item kind `{kind}`, expected `types`, `accounts`, `events`, \
+                         `instructions`, `constants` or `errors`"
+                    ),
+                ));
+            }
+        };
+        if slot.replace(group).is_some() {
+            return Err(syn::Error::new(
+                kind.span(),
+                format!("Duplicate item kind `{kind}`"),
+            ));
+        }
+        if !content.is_empty() {
+            content.parse::<Token![,]>()?;
+        }
+    }
+    Ok(selection)
+}
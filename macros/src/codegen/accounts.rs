@@ -1,7 +1,8 @@
 use quote::{format_ident, quote};
 
 use super::common::{gen_discriminator, gen_type_def};
-use crate::idl::{Idl, IdlSerialization};
+use super::layout::{borsh_space, gen_layout_items};
+use crate::idl::{Idl, IdlDefinedFields, IdlSerialization, IdlType, IdlTypeDefTy};
 
 /// Generate the `accounts` module with account types, discriminators,
 /// `deserialize`, and `fetch` methods.
@@ -81,6 +82,30 @@ pub fn gen_accounts_mod(idl: &Idl) -> proc_macro2::TokenStream {
             }
         };
 
+        let serialize_body = match ty_def.serialization {
+            IdlSerialization::Borsh => {
+                quote! {
+                    writer.write_all(&Self::DISCRIMINATOR)?;
+                    BorshSerialize::serialize(self, writer)
+                }
+            }
+            IdlSerialization::Bytemuck | IdlSerialization::BytemuckUnsafe => {
+                quote! {
+                    writer.write_all(&Self::DISCRIMINATOR)?;
+                    writer.write_all(::anchor_parser::__private::bytes_of(self))
+                }
+            }
+            _ => {
+                quote! {
+                    let _ = writer;
+                    Err(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        "Unsupported serialization format",
+                    ))
+                }
+            }
+        };
+
         // For BytemuckUnsafe, generate unsafe Pod + Zeroable impls
         let bytemuck_impls = match ty_def.serialization {
             IdlSerialization::BytemuckUnsafe => {
@@ -98,24 +123,142 @@ pub fn gen_accounts_mod(idl: &Idl) -> proc_macro2::TokenStream {
             _ => quote!(),
         };
 
+        let zero_copy_fns = match ty_def.serialization {
+            IdlSerialization::Bytemuck | IdlSerialization::BytemuckUnsafe => {
+                quote! {
+                    impl #name {
+                        /// Borrow from raw account data (including discriminator
+                        /// prefix) without copying.
+                        ///
+                        /// Fails if the payload after the discriminator isn't
+                        /// aligned for `Self`; use [`Self::from_account_data`] to
+                        /// copy it instead.
+                        ///
+                        /// On 64-bit hosts, `u128` / `i128` fields require 16-byte
+                        /// alignment, so a plain `Vec<u8>` from RPC (payload at
+                        /// offset 8) will usually be rejected for such accounts.
+                        pub fn from_account_data_ref(data: &[u8]) -> Result<&Self, std::io::Error> {
+                            ::anchor_parser::__private::bytemuck_ref(data, &Self::DISCRIMINATOR)
+                        }
+
+                        /// Mutably borrow from raw account data (including
+                        /// discriminator prefix) without copying, e.g. to modify
+                        /// an account for local simulation.
+                        ///
+                        /// Fails if the payload after the discriminator isn't
+                        /// aligned for `Self`.
+                        pub fn from_account_data_mut(
+                            data: &mut [u8],
+                        ) -> Result<&mut Self, std::io::Error> {
+                            ::anchor_parser::__private::bytemuck_mut(data, &Self::DISCRIMINATOR)
+                        }
+                    }
+                }
+            }
+            _ => quote!(),
+        };
+
+        let size_items = match ty_def.serialization {
+            IdlSerialization::Borsh => {
+                let init_space = borsh_space(ty_def, &idl.types).map(|space| {
+                    quote! {
+                        /// Maximum size of the Borsh payload, excluding the
+                        /// discriminator, as computed by Anchor's `InitSpace`.
+                        pub const INIT_SPACE: usize = #space;
+                    }
+                });
+                quote! {
+                    #init_space
+
+                    /// Length of the account data this value serializes to,
+                    /// including the discriminator.
+                    ///
+                    /// Fails where [`Self::to_account_data`] would, e.g. on NaN
+                    /// floats, which Borsh rejects.
+                    pub fn serialized_len(&self) -> Result<usize, std::io::Error> {
+                        Ok(#disc_len + object_length(self)?)
+                    }
+                }
+            }
+            IdlSerialization::Bytemuck | IdlSerialization::BytemuckUnsafe => {
+                quote! {
+                    /// Account data size: discriminator plus the zero-copy payload.
+                    pub const SIZE: usize = #disc_len + std::mem::size_of::<Self>();
+                }
+            }
+            _ => quote!(),
+        };
+
+        let layout_items = gen_layout_items(ty_def, &idl.types, disc_len);
+
+        let pubkey_field_arms: Vec<_> = match &ty_def.ty {
+            IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(fields)),
+            } => fields
+                .iter()
+                .filter(|field| field.ty == IdlType::Pubkey)
+                .map(|field| {
+                    let field_name = format_ident!("{}", field.name);
+                    let field_str = &field.name;
+                    quote!(#field_str => Some(self.#field_name))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
         quote! {
             #struct_def
 
             #bytemuck_impls
 
+            #zero_copy_fns
+
+            impl ::anchor_parser::AccountFields for #name {
+                fn pubkey_field(&self, name: &str) -> Option<Pubkey> {
+                    match name {
+                        #(#pubkey_field_arms,)*
+                        _ => None,
+                    }
+                }
+            }
+
             impl ::anchor_parser::AccountDeserialize for #name {
                 const DISCRIMINATOR: &'static [u8] = &#disc;
 
                 #deserialize_body
             }
 
+            impl ::anchor_parser::AccountSerialize for #name {
+                fn try_serialize<W: std::io::Write>(
+                    &self,
+                    writer: &mut W,
+                ) -> Result<(), std::io::Error> {
+                    #serialize_body
+                }
+            }
+
             impl #name {
                 pub const DISCRIMINATOR: [u8; #disc_len] = #disc;
 
+                #size_items
+
+                #layout_items
+
                 /// Deserialize from raw account data (including discriminator prefix).
                 pub fn from_account_data(data: &[u8]) -> Result<Self, std::io::Error> {
                     <Self as ::anchor_parser::AccountDeserialize>::deserialize(data)
                 }
+
+                /// Serialize to raw account data (including discriminator prefix).
+                pub fn to_account_data(&self) -> Result<Vec<u8>, std::io::Error> {
+                    ::anchor_parser::AccountSerialize::to_account_data(self)
+                }
+
+                /// Serialize to raw account data zero-padded to `len` bytes,
+                /// e.g. the account's original allocation size.
+                pub fn to_account_data_padded(&self, len: usize) -> Result<Vec<u8>, std::io::Error> {
+                    ::anchor_parser::AccountSerialize::to_account_data_padded(self, len)
+                }
             }
         }
     });
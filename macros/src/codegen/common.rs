@@ -22,8 +22,8 @@ pub fn convert_idl_type_to_tokens(ty: &IdlType, is_const: bool) -> proc_macro2::
         IdlType::F64 => quote!(f64),
         IdlType::U128 => quote!(u128),
         IdlType::I128 => quote!(i128),
-        IdlType::U256 => quote!([u8; 32]), // u256 not native, represent as bytes
-        IdlType::I256 => quote!([u8; 32]),
+        IdlType::U256 => quote!(::anchor_parser::num::U256),
+        IdlType::I256 => quote!(::anchor_parser::num::I256),
         IdlType::Bytes => {
             if is_const {
                 quote!(&'static [u8])
@@ -133,6 +133,16 @@ pub fn gen_type_def(ty_def: &IdlTypeDef, all_ty_defs: &[IdlTypeDef]) -> proc_mac
 
     // Derives & attributes
     let (derives, repr_attr) = gen_derives_and_repr(ty_def, all_ty_defs);
+    let type_params: Vec<_> = ty_def
+        .generics
+        .iter()
+        .filter_map(|g| match g {
+            IdlTypeDefGeneric::Type { name } => Some(format_ident!("{}", name)),
+            IdlTypeDefGeneric::Const { .. } => None,
+        })
+        .collect();
+    let serde_derive = gen_serde_derive(&type_params);
+    let serde_field_impl = gen_serde_field_impl(ty_def);
 
     match &ty_def.ty {
         IdlTypeDefTy::Struct { fields } => {
@@ -143,14 +153,16 @@ pub fn gen_type_def(ty_def: &IdlTypeDef, all_ty_defs: &[IdlTypeDef]) -> proc_mac
                         let fname = format_ident!("{}", f.name);
                         let fty = convert_idl_type_to_tokens(&f.ty, false);
                         let fdocs = gen_docs(&f.docs);
-                        quote! { #fdocs pub #fname: #fty }
+                        let fserde = gen_serde_field_attr(&f.ty);
+                        quote! { #fdocs #fserde pub #fname: #fty }
                     });
                     quote!({ #(#fs,)* })
                 }
                 Some(IdlDefinedFields::Tuple(tys)) => {
                     let fs = tys.iter().map(|t| {
                         let ty = convert_idl_type_to_tokens(t, false);
-                        quote!(pub #ty)
+                        let serde = gen_serde_field_attr(t);
+                        quote!(#serde pub #ty)
                     });
                     quote!((#(#fs,)*);)
                 }
@@ -158,8 +170,11 @@ pub fn gen_type_def(ty_def: &IdlTypeDef, all_ty_defs: &[IdlTypeDef]) -> proc_mac
             quote! {
                 #docs
                 #derives
+                #serde_derive
                 #repr_attr
                 pub struct #name #generics #body
+
+                #serde_field_impl
             }
         }
         IdlTypeDefTy::Enum { variants } => {
@@ -171,12 +186,17 @@ pub fn gen_type_def(ty_def: &IdlTypeDef, all_ty_defs: &[IdlTypeDef]) -> proc_mac
                         let fs = fields.iter().map(|f| {
                             let fname = format_ident!("{}", f.name);
                             let fty = convert_idl_type_to_tokens(&f.ty, false);
-                            quote!(#fname: #fty)
+                            let fserde = gen_serde_field_attr(&f.ty);
+                            quote!(#fserde #fname: #fty)
                         });
                         quote!(#vname { #(#fs,)* })
                     }
                     Some(IdlDefinedFields::Tuple(tys)) => {
-                        let fs = tys.iter().map(|t| convert_idl_type_to_tokens(t, false));
+                        let fs = tys.iter().map(|t| {
+                            let ty = convert_idl_type_to_tokens(t, false);
+                            let serde = gen_serde_field_attr(t);
+                            quote!(#serde #ty)
+                        });
                         quote!(#vname(#(#fs,)*))
                     }
                 }
@@ -184,10 +204,13 @@ pub fn gen_type_def(ty_def: &IdlTypeDef, all_ty_defs: &[IdlTypeDef]) -> proc_mac
             quote! {
                 #docs
                 #derives
+                #serde_derive
                 #repr_attr
                 pub enum #name #generics {
                     #(#vs,)*
                 }
+
+                #serde_field_impl
             }
         }
         IdlTypeDefTy::Type { alias } => {
@@ -272,6 +295,101 @@ fn gen_derives_and_repr(
     (derives, repr)
 }
 
+// ── Serde ────────────────────────────────────────────────────────────
+
+/// Whether generated types derive `Serialize`/`Deserialize`, set through the
+/// `serde` feature of `anchor-parser`.
+const SERDE: bool = cfg!(feature = "serde");
+
+/// `Serialize`/`Deserialize` derives for a generated type, bounding its type
+/// parameters by `SerdeField`. Empty without the `serde` feature.
+pub fn gen_serde_derive(type_params: &[proc_macro2::Ident]) -> proc_macro2::TokenStream {
+    if !SERDE {
+        return quote!();
+    }
+    let bound = type_params
+        .iter()
+        .map(|param| format!("{param}: ::anchor_parser::serde::SerdeField"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    quote! {
+        #[derive(
+            ::anchor_parser::__private::serde::Serialize,
+            ::anchor_parser::__private::serde::Deserialize
+        )]
+        #[serde(crate = "::anchor_parser::__private::serde", bound = #bound)]
+    }
+}
+
+/// `#[serde(with = ...)]` for fields whose type needs the representation
+/// of `anchor_parser::serde::SerdeField`: pubkeys, 64-bit and wider
+/// integers, arrays, generic parameters and containers of those. Types
+/// whose derived representation already matches are left alone. Defined
+/// types always go through `SerdeField`: generated structs and enums
+/// delegate to their derived impls, and aliases resolve to the impl of
+/// the aliased type.
+pub fn gen_serde_field_attr(ty: &IdlType) -> proc_macro2::TokenStream {
+    let native = matches!(
+        ty,
+        IdlType::Bool
+            | IdlType::U8
+            | IdlType::I8
+            | IdlType::U16
+            | IdlType::I16
+            | IdlType::U32
+            | IdlType::I32
+            | IdlType::F32
+            | IdlType::F64
+            | IdlType::Bytes
+            | IdlType::String
+    );
+    if !SERDE || native {
+        return quote!();
+    }
+    quote!(#[serde(with = "::anchor_parser::serde::field")])
+}
+
+/// `SerdeField` impl delegating to the derived impls, so the type can
+/// appear inside `Option`, `Vec` and arrays of other generated types.
+fn gen_serde_field_impl(ty_def: &IdlTypeDef) -> proc_macro2::TokenStream {
+    if !SERDE {
+        return quote!();
+    }
+    let name = format_ident!("{}", ty_def.name);
+    let impl_params = ty_def.generics.iter().map(|g| match g {
+        IdlTypeDefGeneric::Type { name } => {
+            let n = format_ident!("{}", name);
+            quote!(#n: ::anchor_parser::serde::SerdeField)
+        }
+        IdlTypeDefGeneric::Const { name, ty } => {
+            let n = format_ident!("{}", name);
+            let t = format_ident!("{}", ty);
+            quote!(const #n: #t)
+        }
+    });
+    let type_args = ty_def.generics.iter().map(|g| match g {
+        IdlTypeDefGeneric::Type { name } | IdlTypeDefGeneric::Const { name, .. } => {
+            format_ident!("{}", name)
+        }
+    });
+    quote! {
+        impl<#(#impl_params),*> ::anchor_parser::serde::SerdeField for #name<#(#type_args),*> {
+            fn serialize<S: ::anchor_parser::__private::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                ::anchor_parser::__private::serde::Serialize::serialize(self, serializer)
+            }
+
+            fn deserialize<'de, D: ::anchor_parser::__private::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                ::anchor_parser::__private::serde::Deserialize::deserialize(deserializer)
+            }
+        }
+    }
+}
+
 // ── Copy / Default derivability checks ───────────────────────────────
 
 fn can_derive_copy(ty_def: &IdlTypeDef, all: &[IdlTypeDef]) -> bool {
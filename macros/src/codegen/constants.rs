@@ -48,6 +48,13 @@ fn gen_const_value(ty: &IdlType, value: &str) -> proc_macro2::TokenStream {
             let tokens: proc_macro2::TokenStream = value.parse().unwrap();
             quote! { &#tokens }
         }
+        IdlType::U256 | IdlType::I256 => {
+            let bytes = decimal_to_le_bytes(value)
+                .unwrap_or_else(|| panic!("Invalid 256-bit integer constant '{}'", value));
+            let byte_lits = bytes.iter().copied();
+            let ty = convert_idl_type_to_tokens(ty, true);
+            quote! { #ty::from_le_bytes([#(#byte_lits),*]) }
+        }
         // For everything else, the value is already a valid Rust expression
         _ => {
             let tokens: proc_macro2::TokenStream = value.parse().unwrap();
@@ -55,3 +62,35 @@ fn gen_const_value(ty: &IdlType, value: &str) -> proc_macro2::TokenStream {
         }
     }
 }
+
+/// Little-endian two's complement bytes of a decimal integer literal,
+/// e.g. `"-1_000"`. `None` on invalid digits or overflow.
+fn decimal_to_le_bytes(value: &str) -> Option<[u8; 32]> {
+    let value = value.trim();
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let mut bytes = [0u8; 32];
+    let mut any = false;
+    for c in digits.chars().filter(|&c| c != '_') {
+        let mut carry = c.to_digit(10)?;
+        any = true;
+        for byte in bytes.iter_mut() {
+            let v = u32::from(*byte) * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    if negative {
+        // Negate: invert and add one
+        let mut carry = true;
+        for byte in bytes.iter_mut() {
+            (*byte, carry) = (!*byte).overflowing_add(carry as u8);
+        }
+    }
+    any.then_some(bytes)
+}
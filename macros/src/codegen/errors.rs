@@ -0,0 +1,174 @@
+use quote::{format_ident, quote};
+
+use crate::idl::Idl;
+
+/// Generate the `errors` module with the program's `ErrorCode` enum.
+pub fn gen_errors_mod(idl: &Idl) -> proc_macro2::TokenStream {
+    let error_enum = gen_error_enum(idl);
+
+    quote! {
+        /// Program error codes.
+        pub mod errors {
+            use ::anchor_parser::__private::*;
+
+            #error_enum
+        }
+    }
+}
+
+fn gen_error_enum(idl: &Idl) -> proc_macro2::TokenStream {
+    if idl.errors.is_empty() {
+        return quote!();
+    }
+
+    let variants: Vec<_> = idl
+        .errors
+        .iter()
+        .map(|err| {
+            let name = format_ident!("{}", err.name);
+            let code = err.code;
+            let doc = err.msg.as_ref().map(|msg| {
+                let s = format!(" {msg}");
+                quote!(#[doc = #s])
+            });
+            quote! {
+                #doc
+                #name = #code
+            }
+        })
+        .collect();
+
+    let name_arms: Vec<_> = idl
+        .errors
+        .iter()
+        .map(|err| {
+            let name = format_ident!("{}", err.name);
+            let name_str = &err.name;
+            quote!(ErrorCode::#name => #name_str)
+        })
+        .collect();
+
+    let msg_arms: Vec<_> = idl
+        .errors
+        .iter()
+        .map(|err| {
+            let name = format_ident!("{}", err.name);
+            let msg = err.msg.as_deref().unwrap_or(&err.name);
+            quote!(ErrorCode::#name => #msg)
+        })
+        .collect();
+
+    let from_code_arms: Vec<_> = idl
+        .errors
+        .iter()
+        .map(|err| {
+            let name = format_ident!("{}", err.name);
+            let code = err.code;
+            quote!(#code => Ok(ErrorCode::#name))
+        })
+        .collect();
+
+    quote! {
+        /// Enum of all custom program errors declared in the IDL.
+        ///
+        /// The discriminant of each variant is its on-chain error number, as
+        /// found in `InstructionError::Custom(n)`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(u32)]
+        pub enum ErrorCode {
+            #(#variants,)*
+        }
+
+        impl ErrorCode {
+            /// On-chain error number.
+            pub const fn code(&self) -> u32 {
+                *self as u32
+            }
+
+            /// Error name as declared in the program.
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    #(#name_arms,)*
+                }
+            }
+
+            /// Error message, falling back to the name when the IDL has none.
+            pub const fn msg(&self) -> &'static str {
+                match self {
+                    #(#msg_arms,)*
+                }
+            }
+
+            /// Decode the custom error of a failed transaction.
+            ///
+            /// Looks up the top-level instruction that failed in `message` and
+            /// returns `None` unless it targets this program and the error is
+            /// one of this program's custom errors.
+            ///
+            /// Errors raised by a CPI into this program are reported against
+            /// the calling instruction; use [`ErrorCode::from_logs`] for those.
+            pub fn from_transaction_error(
+                err: &TransactionError,
+                message: &VersionedMessage,
+            ) -> Option<Self> {
+                let (index, code) = ::anchor_parser::error::custom_error_code(err)?;
+                let ix = message.instructions().get(index as usize)?;
+                let program_id = message
+                    .static_account_keys()
+                    .get(ix.program_id_index as usize)?;
+                if *program_id != super::ID {
+                    return None;
+                }
+                Self::try_from(code).ok()
+            }
+
+            /// Convert a parsed Anchor error log into this program's error.
+            ///
+            /// Returns `None` if the log was attributed to another program or
+            /// the error number is not one of this program's custom errors
+            /// (e.g. Anchor's built-in constraint errors).
+            pub fn from_anchor_error(log: &::anchor_parser::error::AnchorErrorLog) -> Option<Self> {
+                if log.program_id.is_some_and(|id| id != super::ID) {
+                    return None;
+                }
+                Self::try_from(log.error_number).ok()
+            }
+
+            /// Find this program's error in a transaction's log messages.
+            ///
+            /// Parses the `"Program log: AnchorError ..."` lines, skipping
+            /// errors attributed to other programs; use
+            /// [`AnchorErrorLog::from_logs`](::anchor_parser::error::AnchorErrorLog::from_logs)
+            /// directly for the source location and compared values.
+            pub fn from_logs<T, I>(logs: T) -> Option<Self>
+            where
+                T: IntoIterator<Item = I>,
+                I: AsRef<str>,
+            {
+                ::anchor_parser::error::AnchorErrorLog::all_from_logs(logs)
+                    .iter()
+                    .find_map(Self::from_anchor_error)
+            }
+        }
+
+        impl TryFrom<u32> for ErrorCode {
+            /// The unknown error number.
+            type Error = u32;
+
+            fn try_from(code: u32) -> Result<Self, Self::Error> {
+                match code {
+                    #(#from_code_arms,)*
+                    _ => Err(code),
+                }
+            }
+        }
+
+        impl std::fmt::Display for ErrorCode {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.msg())
+            }
+        }
+
+        impl std::error::Error for ErrorCode {}
+    }
+}
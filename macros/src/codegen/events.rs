@@ -3,7 +3,7 @@ use quote::{format_ident, quote};
 use super::common::{gen_discriminator, gen_type_def};
 use crate::idl::Idl;
 
-/// Generate the `events` module with event types, discriminators, and `from_logs`.
+/// Generate the `events` module with event types, discriminators, and log parsers.
 pub fn gen_events_mod(idl: &Idl) -> proc_macro2::TokenStream {
     let events = idl.events.iter().map(|ev| {
         let name = format_ident!("{}", ev.name);
@@ -24,10 +24,18 @@ pub fn gen_events_mod(idl: &Idl) -> proc_macro2::TokenStream {
             impl #name {
                 pub const DISCRIMINATOR: [u8; #disc_len] = #disc;
 
+                /// Decode from the event discriminator and Borsh payload.
+                pub fn decode(data: &[u8]) -> Option<Self> {
+                    if data.len() < #disc_len || data[..#disc_len] != Self::DISCRIMINATOR {
+                        return None;
+                    }
+                    ::anchor_parser::__private::BorshDeserialize::try_from_slice(&data[#disc_len..]).ok()
+                }
+
                 /// Parse all occurrences of this event from `emit!` log lines.
                 ///
                 /// Matches every `"Program data: <base64>"` line regardless of
-                /// which program emitted it.
+                /// which program emitted it; see [`Self::from_program_logs`].
                 pub fn from_logs<T, I>(logs: T) -> Vec<Self>
                 where
                     T: IntoIterator<Item = I>,
@@ -37,16 +45,86 @@ pub fn gen_events_mod(idl: &Idl) -> proc_macro2::TokenStream {
                         .filter_map(|log| {
                             let data_str = log.as_ref().strip_prefix("Program data: ")?;
                             let data = ::anchor_parser::__private::base64_decode(data_str)?;
-                            if data.len() < #disc_len {
-                                return None;
-                            }
-                            if data[..#disc_len] != Self::DISCRIMINATOR {
-                                return None;
-                            }
-                            ::anchor_parser::__private::BorshDeserialize::try_from_slice(
-                                &data[#disc_len..],
+                            Self::decode(&data)
+                        })
+                        .collect()
+                }
+
+                /// Lazily parse occurrences of this event from `emit!` log lines.
+                ///
+                /// Like [`Self::from_logs`], but decodes one line at a time into
+                /// a single reused buffer, and only base64-decodes a line in
+                /// full once its leading bytes match [`Self::DISCRIMINATOR`].
+                pub fn iter_logs<T, I>(logs: T) -> impl Iterator<Item = Self>
+                where
+                    T: IntoIterator<Item = I>,
+                    I: AsRef<str>,
+                {
+                    let mut decoder = ::anchor_parser::logs::ProgramDataDecoder::new();
+                    logs.into_iter().filter_map(move |log| {
+                        let data = decoder.decode(log.as_ref(), |prefix| {
+                            ::anchor_parser::logs::ProgramDataDecoder::prefix_matches(
+                                prefix,
+                                &Self::DISCRIMINATOR,
                             )
-                            .ok()
+                        })?;
+                        Self::decode(data)
+                    })
+                }
+
+                /// Parse all occurrences of this event from `emit!` log lines,
+                /// reporting payloads that fail to deserialize.
+                ///
+                /// Yields one result per `"Program data: <base64>"` line that
+                /// starts with [`Self::DISCRIMINATOR`]; other lines are skipped.
+                pub fn try_from_logs<T, I>(
+                    logs: T,
+                ) -> Vec<Result<Self, ::anchor_parser::event::EventDecodeError>>
+                where
+                    T: IntoIterator<Item = I>,
+                    I: AsRef<str>,
+                {
+                    logs.into_iter()
+                        .enumerate()
+                        .filter_map(|(index, log)| {
+                            let data_str = log.as_ref().strip_prefix("Program data: ")?;
+                            let data = ::anchor_parser::__private::base64_decode(data_str)?;
+                            Self::try_decode_at(index, &data)
+                        })
+                        .collect()
+                }
+
+                /// Decode `data` if it starts with [`Self::DISCRIMINATOR`],
+                /// reporting a payload that fails to deserialize.
+                fn try_decode_at(
+                    index: usize,
+                    data: &[u8],
+                ) -> Option<Result<Self, ::anchor_parser::event::EventDecodeError>> {
+                    if data.len() < #disc_len || data[..#disc_len] != Self::DISCRIMINATOR {
+                        return None;
+                    }
+                    Some(
+                        ::anchor_parser::__private::BorshDeserialize::try_from_slice(&data[#disc_len..])
+                            .map_err(|error| {
+                                ::anchor_parser::event::EventDecodeError::deserialize(index, data, #disc_len, error)
+                            }),
+                    )
+                }
+
+                /// Parse all occurrences of this event emitted by this program
+                /// through `emit!`.
+                ///
+                /// Follows the `Program <id> invoke [n]` / `success` / `failed`
+                /// lines and only decodes `"Program data: <base64>"` lines
+                /// written while [`ID`](super::ID) is on top of the invoke stack.
+                pub fn from_program_logs<T, I>(logs: T) -> Vec<::anchor_parser::event::LoggedEvent<Self>>
+                where
+                    T: IntoIterator<Item = I>,
+                    I: AsRef<str>,
+                {
+                    ::anchor_parser::logs::program_data(super::ID, logs)
+                        .filter_map(|(context, data)| {
+                            Some(::anchor_parser::event::LoggedEvent::new(Self::decode(&data)?, context))
                         })
                         .collect()
                 }
@@ -60,28 +138,63 @@ pub fn gen_events_mod(idl: &Idl) -> proc_macro2::TokenStream {
                 ///
                 /// The expected binary layout is:
                 /// `[8-byte CPI event tag][8-byte event discriminator][borsh payload]`
+                ///
+                /// Entries that do not start with Anchor's
+                /// [`EVENT_IX_TAG`](::anchor_parser::event::EVENT_IX_TAG) are
+                /// skipped. The data alone cannot tell which program was
+                /// invoked; use [`Self::from_cpi_instruction`] to also check
+                /// the program ID and event authority.
                 pub fn from_cpi_logs<T, I>(logs: T) -> Vec<Self>
                 where
                     T: IntoIterator<Item = I>,
                     I: AsRef<str>,
                 {
-                    const CPI_TAG_LEN: usize = 8;
                     logs.into_iter()
                         .filter_map(|log| {
                             let raw = ::anchor_parser::__private::bs58_decode(log.as_ref())?;
-                            if raw.len() < CPI_TAG_LEN + #disc_len {
-                                return None;
-                            }
-                            if raw[CPI_TAG_LEN..CPI_TAG_LEN + #disc_len] != Self::DISCRIMINATOR {
-                                return None;
-                            }
-                            ::anchor_parser::__private::BorshDeserialize::try_from_slice(
-                                &raw[CPI_TAG_LEN + #disc_len..],
-                            )
-                            .ok()
+                            Self::decode(::anchor_parser::event::strip_event_ix_tag(&raw)?)
+                        })
+                        .collect()
+                }
+
+                /// Parse all occurrences of this event from `emit_cpi!` inner
+                /// instruction data, reporting payloads that fail to
+                /// deserialize.
+                ///
+                /// Yields one result per entry that starts with Anchor's event
+                /// tag and [`Self::DISCRIMINATOR`]; other entries are skipped.
+                pub fn try_from_cpi_logs<T, I>(
+                    logs: T,
+                ) -> Vec<Result<Self, ::anchor_parser::event::EventDecodeError>>
+                where
+                    T: IntoIterator<Item = I>,
+                    I: AsRef<str>,
+                {
+                    logs.into_iter()
+                        .enumerate()
+                        .filter_map(|(index, log)| {
+                            let raw = ::anchor_parser::__private::bs58_decode(log.as_ref())?;
+                            Self::try_decode_at(index, ::anchor_parser::event::strip_event_ix_tag(&raw)?)
                         })
                         .collect()
                 }
+
+                /// Decode this event from an `emit_cpi!` inner instruction.
+                ///
+                /// Returns `None` unless the instruction targets [`ID`](super::ID),
+                /// is signed by the program's [`event_authority`], and its
+                /// data starts with Anchor's event tag and this event's
+                /// discriminator.
+                pub fn from_cpi_instruction(
+                    program_id: &Pubkey,
+                    accounts: &[Pubkey],
+                    data: &[u8],
+                ) -> Option<Self> {
+                    if !is_event_cpi(program_id, accounts) {
+                        return None;
+                    }
+                    Self::decode(::anchor_parser::event::strip_event_ix_tag(data)?)
+                }
             }
         }
     });
@@ -93,6 +206,19 @@ pub fn gen_events_mod(idl: &Idl) -> proc_macro2::TokenStream {
             #[allow(unused_imports)]
             use super::types::*;
 
+            /// Address of the program's event authority, the PDA that signs
+            /// its `emit_cpi!` self-CPIs.
+            pub fn event_authority() -> Pubkey {
+                static EVENT_AUTHORITY: std::sync::OnceLock<Pubkey> = std::sync::OnceLock::new();
+                *EVENT_AUTHORITY.get_or_init(|| ::anchor_parser::event::event_authority(&super::ID))
+            }
+
+            /// Whether an inner instruction with these program ID and accounts
+            /// is one of the program's `emit_cpi!` self-CPIs.
+            pub fn is_event_cpi(program_id: &Pubkey, accounts: &[Pubkey]) -> bool {
+                *program_id == super::ID && accounts.first() == Some(&event_authority())
+            }
+
             #(#events)*
         }
     }
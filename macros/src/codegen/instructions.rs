@@ -1,15 +1,26 @@
 use heck::ToUpperCamelCase;
 use quote::{format_ident, quote};
 
-use super::common::{convert_idl_type_to_tokens, flatten_accounts, gen_discriminator, gen_docs};
-use crate::idl::Idl;
+use super::common::{
+    FlatAccount, convert_idl_type_to_tokens, flatten_accounts, gen_discriminator, gen_docs,
+    gen_serde_derive, gen_serde_field_attr,
+};
+use super::pda::{
+    ResolvedSeed, ScopedAccount, SeedSource, SeedType, instruction_accounts, resolve_pda,
+};
+use crate::idl::{
+    Idl, IdlDefinedFields, IdlInstruction, IdlInstructionAccountItem, IdlSeed, IdlType,
+    IdlTypeDefTy,
+};
 
 /// Generate the `instructions` module with account structs and builder functions.
 pub fn gen_instructions_mod(idl: &Idl) -> proc_macro2::TokenStream {
     let instructions = idl.instructions.iter().map(|ix| {
         let fn_name = format_ident!("{}", ix.name);
         let accounts_struct_name = format_ident!("{}Accounts", ix.name.to_upper_camel_case());
+        let args_struct_name = args_struct_name(idl, &ix.name);
         let disc = gen_discriminator(&ix.discriminator);
+        let disc_len = ix.discriminator.len();
         let docs = gen_docs(&ix.docs);
         // Flatten accounts
         let flat = flatten_accounts(&ix.accounts, "");
@@ -41,6 +52,102 @@ pub fn gen_instructions_mod(idl: &Idl) -> proc_macro2::TokenStream {
             }
         };
 
+        // Decode accounts struct from an instruction's account list
+        let acc_len = flat.len();
+        let from_keys_fields: Vec<_> = flat
+            .iter()
+            .map(|a| {
+                let field_name = format_ident!("{}", a.name);
+                let name_str = &a.name;
+                if a.optional {
+                    // The program ID stands in for a missing optional account,
+                    // and trailing optional accounts may be omitted entirely
+                    quote! { #field_name: keys.next().filter(|key| key != program_id) }
+                } else {
+                    quote! {
+                        #field_name: keys
+                            .next()
+                            .ok_or_else(|| ::anchor_parser::__private::missing_account(#name_str))?
+                    }
+                }
+            })
+            .collect();
+
+        let from_keys_body = if acc_fields.is_empty() {
+            quote! {
+                let _ = (program_id, keys);
+                Ok(Self)
+            }
+        } else {
+            quote! {
+                let mut keys = keys.into_iter();
+                Ok(Self {
+                    #(#from_keys_fields,)*
+                })
+            }
+        };
+
+        let accounts_impl = quote! {
+            impl #accounts_struct_name {
+                /// Number of accounts the instruction expects, excluding
+                /// remaining accounts.
+                pub const LEN: usize = #acc_len;
+
+                /// Decode from an instruction's account metas.
+                ///
+                /// Optional accounts set to `program_id`, the program the
+                /// instruction targets, are decoded as `None`. Accounts past
+                /// [`Self::LEN`] are remaining accounts and are ignored; see
+                /// [`Self::remaining_accounts`].
+                pub fn from_account_metas(
+                    program_id: &Pubkey,
+                    metas: &[AccountMeta],
+                ) -> Result<Self, std::io::Error> {
+                    Self::from_keys(program_id, metas.iter().map(|meta| meta.pubkey))
+                }
+
+                /// Decode from a compiled instruction's account indices into
+                /// the transaction's account keys.
+                ///
+                /// For versioned transactions, `account_keys` must include the
+                /// addresses loaded from lookup tables (writable, then readonly)
+                /// after the static keys. Optional accounts are handled as in
+                /// [`Self::from_account_metas`].
+                pub fn from_account_keys(
+                    program_id: &Pubkey,
+                    account_keys: &[Pubkey],
+                    indices: &[u8],
+                ) -> Result<Self, std::io::Error> {
+                    let keys = indices
+                        .iter()
+                        .map(|&index| {
+                            account_keys.get(index as usize).copied().ok_or_else(|| {
+                                std::io::Error::new(
+                                    std::io::ErrorKind::InvalidData,
+                                    format!("Account index {index} out of range"),
+                                )
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Self::from_keys(program_id, keys)
+                }
+
+                /// Accounts passed after the instruction's own accounts.
+                pub fn remaining_accounts<T>(accounts: &[T]) -> &[T] {
+                    accounts.get(Self::LEN..).unwrap_or(&[])
+                }
+
+                fn from_keys<I>(program_id: &Pubkey, keys: I) -> Result<Self, std::io::Error>
+                where
+                    I: IntoIterator<Item = Pubkey>,
+                {
+                    #from_keys_body
+                }
+            }
+        };
+
+        let accounts_partial = gen_accounts_partial(idl, ix, &flat);
+
         // Generate AccountMeta entries
         let account_metas: Vec<_> = flat
             .iter()
@@ -110,21 +217,53 @@ pub fn gen_instructions_mod(idl: &Idl) -> proc_macro2::TokenStream {
             quote!(vec![#(#account_metas),*])
         };
 
+        // Generate args struct
+        let args_fields: Vec<_> = ix
+            .args
+            .iter()
+            .map(|arg| {
+                let name = format_ident!("{}", arg.name);
+                let ty = convert_idl_type_to_tokens(&arg.ty, false);
+                let arg_docs = gen_docs(&arg.docs);
+                let arg_serde = gen_serde_field_attr(&arg.ty);
+                quote! { #arg_docs #arg_serde pub #name: #ty }
+            })
+            .collect();
+
+        let args_doc = format!(" Arguments of the `{}` instruction.", ix.name);
+        let args_serde = gen_serde_derive(&[]);
+        let args_struct = if args_fields.is_empty() {
+            quote! {
+                #[doc = #args_doc]
+                #[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+                #args_serde
+                pub struct #args_struct_name;
+            }
+        } else {
+            quote! {
+                #[doc = #args_doc]
+                #[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+                #args_serde
+                pub struct #args_struct_name {
+                    #(#args_fields,)*
+                }
+            }
+        };
+
         let builder_fn = quote! {
             #docs
             pub fn #fn_name(
                 program_id: &Pubkey,
                 #accounts_param
                 #(#fn_params,)*
-            ) -> Instruction {
+            ) -> ::anchor_parser::__private::Instruction {
                 let mut __ix_data = Vec::with_capacity(256);
                 // Write discriminator
-                let __disc: &[u8] = &#disc;
-                __ix_data.extend_from_slice(__disc);
+                __ix_data.extend_from_slice(&#args_struct_name::DISCRIMINATOR);
                 // Serialize args
                 #(#serialize_args)*
 
-                Instruction {
+                ::anchor_parser::__private::Instruction {
                     program_id: *program_id,
                     accounts: #account_metas_expr,
                     data: __ix_data,
@@ -132,20 +271,644 @@ pub fn gen_instructions_mod(idl: &Idl) -> proc_macro2::TokenStream {
             }
         };
 
+        let return_mod = ix.returns.as_ref().map(|returns| {
+            let return_ty = convert_idl_type_to_tokens(returns, false);
+            let mod_doc = format!(" Return value of the `{}` instruction.", ix.name);
+            quote! {
+                #[doc = #mod_doc]
+                pub mod #fn_name {
+                    use ::anchor_parser::__private::*;
+                    #[allow(unused_imports)]
+                    use super::super::types::*;
+                    #[allow(unused_imports)]
+                    use super::super::accounts::*;
+                    #[allow(unused_imports)]
+                    use super::super::events::*;
+
+                    /// Type returned by the instruction.
+                    pub type Return = #return_ty;
+
+                    /// Decode the instruction's return data.
+                    ///
+                    /// Trailing zero bytes trimmed by the runtime are restored.
+                    pub fn decode_return(data: &[u8]) -> Result<Return, std::io::Error> {
+                        ::anchor_parser::return_data::decode(data)
+                    }
+
+                    /// Decode the return value from the last
+                    /// `"Program return: <program_id> <base64>"` line written by
+                    /// [`ID`](super::super::ID).
+                    ///
+                    /// Returns `None` if the program wrote no such line.
+                    pub fn decode_return_log<T, I>(logs: T) -> Option<Result<Return, std::io::Error>>
+                    where
+                        T: IntoIterator<Item = I>,
+                        I: AsRef<str>,
+                    {
+                        let data = ::anchor_parser::return_data::from_logs(&super::super::ID, logs)?;
+                        Some(decode_return(&data))
+                    }
+
+                    /// Decode the return value from the `program_id` and `data`
+                    /// of a transaction's return data, e.g. a simulation's
+                    /// `TransactionReturnData`.
+                    ///
+                    /// Fails if the data was set by another program.
+                    pub fn decode_return_data(
+                        program_id: &Pubkey,
+                        data: &[u8],
+                    ) -> Result<Return, std::io::Error> {
+                        if *program_id != super::super::ID {
+                            return Err(std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("Return data was set by another program: {program_id}"),
+                            ));
+                        }
+                        decode_return(data)
+                    }
+                }
+            }
+        });
+
         quote! {
             #accounts_struct
+            #accounts_impl
+            #accounts_partial
+            #args_struct
+
+            impl #args_struct_name {
+                pub const DISCRIMINATOR: [u8; #disc_len] = #disc;
+            }
+
             #builder_fn
+            #return_mod
         }
     });
 
+    let instruction_enum = gen_instruction_enum(idl);
+
     quote! {
-        /// Instruction builders.
+        /// Instruction builders and decoders.
         pub mod instructions {
             use ::anchor_parser::__private::*;
             #[allow(unused_imports)]
             use super::types::*;
 
             #(#instructions)*
+
+            #instruction_enum
+        }
+    }
+}
+
+/// Name of an instruction's argument struct: `{Ix}Args`, or `{Ix}IxArgs` if
+/// the IDL declares a type of that name (e.g. `initialize(args:
+/// InitializeArgs)`), which the glob-imported types would otherwise clash
+/// with.
+fn args_struct_name(idl: &Idl, ix_name: &str) -> proc_macro2::Ident {
+    let camel = ix_name.to_upper_camel_case();
+    let mut name = format!("{camel}Args");
+    while idl.types.iter().any(|ty| ty.name == name) {
+        name.insert_str(camel.len(), "Ix");
+    }
+    format_ident!("{}", name)
+}
+
+fn gen_instruction_enum(idl: &Idl) -> proc_macro2::TokenStream {
+    if idl.instructions.is_empty() {
+        return quote!();
+    }
+
+    let variants: Vec<_> = idl
+        .instructions
+        .iter()
+        .map(|ix| {
+            let variant = format_ident!("{}", ix.name.to_upper_camel_case());
+            let args_struct_name = args_struct_name(idl, &ix.name);
+            quote!(#variant(#args_struct_name))
+        })
+        .collect();
+
+    let decode_arms: Vec<_> = idl
+        .instructions
+        .iter()
+        .map(|ix| {
+            let variant = format_ident!("{}", ix.name.to_upper_camel_case());
+            let args_struct_name = args_struct_name(idl, &ix.name);
+            let disc_len = ix.discriminator.len();
+            quote! {
+                if data.len() >= #disc_len && data[..#disc_len] == #args_struct_name::DISCRIMINATOR {
+                    let mut reader = &data[#disc_len..];
+                    return BorshDeserialize::deserialize(&mut reader).map(Instruction::#variant);
+                }
+            }
+        })
+        .collect();
+
+    let accounts_variants: Vec<_> = idl
+        .instructions
+        .iter()
+        .map(|ix| {
+            let variant = format_ident!("{}", ix.name.to_upper_camel_case());
+            let accounts_struct_name = format_ident!("{}Accounts", ix.name.to_upper_camel_case());
+            quote!(#variant(#accounts_struct_name))
+        })
+        .collect();
+
+    let accounts_arms: Vec<_> = idl
+        .instructions
+        .iter()
+        .map(|ix| {
+            let variant = format_ident!("{}", ix.name.to_upper_camel_case());
+            let accounts_struct_name = format_ident!("{}Accounts", ix.name.to_upper_camel_case());
+            quote! {
+                Instruction::#variant(_) => #accounts_struct_name::from_account_keys(program_id, account_keys, indices)
+                    .map(InstructionAccounts::#variant)
+            }
+        })
+        .collect();
+
+    let accounts_len_arms: Vec<_> = idl
+        .instructions
+        .iter()
+        .map(|ix| {
+            let variant = format_ident!("{}", ix.name.to_upper_camel_case());
+            let accounts_struct_name = format_ident!("{}Accounts", ix.name.to_upper_camel_case());
+            quote!(InstructionAccounts::#variant(_) => #accounts_struct_name::LEN)
+        })
+        .collect();
+
+    quote! {
+        /// Enum wrapping the decoded arguments of every program instruction.
+        #[derive(Debug, Clone)]
+        pub enum Instruction {
+            #(#variants,)*
+        }
+
+        /// Enum wrapping the decoded accounts of every program instruction.
+        #[derive(Debug, Clone)]
+        pub enum InstructionAccounts {
+            #(#accounts_variants,)*
+        }
+
+        impl InstructionAccounts {
+            /// Decode the accounts of an already decoded instruction from a
+            /// compiled instruction's account indices.
+            ///
+            /// See the `from_account_keys` method of the `*Accounts` structs.
+            pub fn from_account_keys(
+                instruction: &Instruction,
+                program_id: &Pubkey,
+                account_keys: &[Pubkey],
+                indices: &[u8],
+            ) -> Result<Self, std::io::Error> {
+                match instruction {
+                    #(#accounts_arms,)*
+                }
+            }
+
+            /// Number of accounts the instruction expects, excluding
+            /// remaining accounts.
+            pub fn accounts_len(&self) -> usize {
+                match self {
+                    #(#accounts_len_arms,)*
+                }
+            }
+        }
+
+        impl Instruction {
+            /// Decode instruction data (discriminator + Borsh-encoded args).
+            ///
+            /// Trailing bytes after the arguments are ignored, as they are
+            /// by the program itself.
+            pub fn decode(data: &[u8]) -> Result<Self, std::io::Error> {
+                #(#decode_arms)*
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Unknown instruction discriminator",
+                ))
+            }
+        }
+    }
+}
+
+/// How [`gen_accounts_partial`] fills in an account left as `None`.
+enum AccountFill {
+    /// Must be provided by the caller.
+    Provided,
+    /// Fixed address declared in the IDL.
+    Address(Vec<u8>),
+    /// The program itself, as passed to `#[event_cpi]` instructions.
+    Program,
+    /// PDA derived from args and other accounts.
+    Pda(super::pda::ResolvedPda),
+}
+
+/// Point account seeds of a PDA declared in a composite at the flattened
+/// names of the composite's accounts.
+fn prefix_seed_accounts(mut pda: super::pda::ResolvedPda, prefix: &str) -> super::pda::ResolvedPda {
+    for seed in pda.seeds.iter_mut().chain(&mut pda.program) {
+        if let ResolvedSeed::Param {
+            source: SeedSource::Account(account),
+            ..
+        } = seed
+        {
+            account.insert_str(0, prefix);
+        }
+    }
+    pda
+}
+
+/// An account whose decoded data stores the addresses of other accounts of
+/// the instruction, as declared by their `relations`.
+struct RelationSource<'a> {
+    /// Flattened name of the account.
+    name: String,
+    /// Generated account type of its data, when the IDL gives it.
+    account_ty: Option<&'a str>,
+    /// Flattened names of the related accounts, with the data field storing
+    /// each address.
+    related: Vec<(&'a str, &'a str)>,
+}
+
+/// Account type of `source`, taken from the seeds in the same accounts
+/// struct that read its data (`source.field`).
+fn seed_account_type<'a>(
+    defs: &[ScopedAccount<'a>],
+    prefix: &str,
+    source: &str,
+) -> Option<&'a str> {
+    defs.iter()
+        .filter(|scoped| scoped.prefix == prefix)
+        .filter_map(|scoped| scoped.account.pda.as_ref())
+        .flat_map(|pda| pda.seeds.iter().chain(&pda.program))
+        .find_map(|seed| match seed {
+            IdlSeed::Account(seed)
+                if seed
+                    .path
+                    .split_once('.')
+                    .is_some_and(|(account, _)| account == source) =>
+            {
+                seed.account.as_deref()
+            }
+            _ => None,
+        })
+}
+
+/// Whether the type `ty` has a `Pubkey` field named `field`.
+fn has_pubkey_field(idl: &Idl, ty: &str, field: &str) -> bool {
+    idl.types.iter().any(|ty_def| {
+        ty_def.name == ty
+            && matches!(
+                &ty_def.ty,
+                IdlTypeDefTy::Struct { fields: Some(IdlDefinedFields::Named(fields)) }
+                    if fields.iter().any(|f| f.name == field && f.ty == IdlType::Pubkey)
+            )
+    })
+}
+
+/// Generate the `{Ix}AccountsPartial` struct, whose `resolve` method fills in
+/// fixed addresses and derivable PDAs.
+fn gen_accounts_partial(
+    idl: &Idl,
+    ix: &IdlInstruction,
+    flat: &[FlatAccount],
+) -> proc_macro2::TokenStream {
+    if flat.is_empty() {
+        return quote!();
+    }
+
+    let camel = ix.name.to_upper_camel_case();
+    let partial_name = format_ident!("{}AccountsPartial", camel);
+    let accounts_struct_name = format_ident!("{}Accounts", camel);
+    let args_struct_name = args_struct_name(idl, &ix.name);
+
+    // `flatten_accounts` and `instruction_accounts` walk accounts in the same
+    // order; seed paths are relative to the enclosing accounts struct
+    let defs = instruction_accounts(&ix.accounts);
+    let has_event_authority = defs
+        .iter()
+        .any(|scoped| scoped.account.name == "event_authority");
+    let mut fills: Vec<AccountFill> = flat
+        .iter()
+        .zip(&defs)
+        .map(|(flat_acc, scoped)| {
+            let def = scoped.account;
+            if flat_acc.optional {
+                AccountFill::Provided
+            } else if let Some(bytes) = def
+                .address
+                .as_ref()
+                .and_then(|address| bs58::decode(address).into_vec().ok())
+                .filter(|bytes| bytes.len() == 32)
+            {
+                AccountFill::Address(bytes)
+            } else if def.name == "program" && has_event_authority {
+                AccountFill::Program
+            } else if let Some(pda) = def
+                .pda
+                .as_ref()
+                .and_then(|pda| resolve_pda(idl, ix, scoped.scope, pda))
+            {
+                AccountFill::Pda(prefix_seed_accounts(pda, &scoped.prefix))
+            } else {
+                AccountFill::Provided
+            }
+        })
+        .collect();
+
+    // Order PDAs after the accounts their seeds refer to, and leave PDAs with
+    // unresolvable seeds to the caller
+    let mut available: Vec<&str> = flat
+        .iter()
+        .zip(&fills)
+        .filter(|(_, fill)| !matches!(fill, AccountFill::Pda(_)))
+        .map(|(acc, _)| acc.name.as_str())
+        .collect();
+    let mut pda_order: Vec<usize> = Vec::new();
+    loop {
+        let ready = fills.iter().enumerate().position(|(i, fill)| {
+            let AccountFill::Pda(pda) = fill else {
+                return false;
+            };
+            !pda_order.contains(&i)
+                && pda.seeds.iter().chain(&pda.program).all(|seed| match seed {
+                    ResolvedSeed::Const(_) => true,
+                    ResolvedSeed::Param { source, .. } => match source {
+                        SeedSource::Arg(_) => true,
+                        SeedSource::Account(name) => available.contains(&name.as_str()),
+                        SeedSource::AccountData => false,
+                    },
+                })
+        });
+        let Some(i) = ready else {
+            break;
+        };
+        available.push(&flat[i].name);
+        pda_order.push(i);
+    }
+    for (i, fill) in fills.iter_mut().enumerate() {
+        if matches!(fill, AccountFill::Pda(_)) && !pda_order.contains(&i) {
+            *fill = AccountFill::Provided;
+        }
+    }
+
+    // Accounts listed in `relations` store the related account's address in
+    // a field named after it. Relations name accounts of the same accounts
+    // struct, so inside a composite both sides get the composite's prefix.
+    let mut relation_sources: Vec<RelationSource> = Vec::new();
+    for (acc, scoped) in flat.iter().zip(&defs) {
+        for source in &scoped.account.relations {
+            let in_scope = scoped.scope.iter().any(|item| {
+                matches!(item, IdlInstructionAccountItem::Single(other) if other.name == *source)
+            });
+            if !in_scope {
+                continue;
+            }
+            let name = format!("{}{source}", scoped.prefix);
+            let related = (acc.name.as_str(), scoped.account.name.as_str());
+            match relation_sources.iter_mut().find(|other| other.name == name) {
+                Some(other) => other.related.push(related),
+                None => relation_sources.push(RelationSource {
+                    account_ty: seed_account_type(&defs, &scoped.prefix, source),
+                    name,
+                    related: vec![related],
+                }),
+            }
+        }
+    }
+    for source in &mut relation_sources {
+        // Only use the account type if it stores every related address
+        source.account_ty = source.account_ty.filter(|ty| {
+            idl.accounts.iter().any(|acc| acc.name == *ty)
+                && source
+                    .related
+                    .iter()
+                    .all(|(_, field)| has_pubkey_field(idl, ty, field))
+        });
+    }
+
+    let fields = flat
+        .iter()
+        .zip(&defs)
+        .zip(&fills)
+        .map(|((acc, scoped), fill)| {
+            let def = scoped.account;
+            let name = format_ident!("{}", acc.name);
+            let with_data_fns: Vec<_> = relation_sources
+                .iter()
+                .filter(|source| source.related.iter().any(|(name, _)| *name == acc.name))
+                .map(|source| format!("[`Self::with_{}_data`]", source.name))
+                .collect();
+            let doc = match fill {
+                AccountFill::Provided if acc.optional => " Optional.".to_string(),
+                AccountFill::Provided if def.pda.is_some() => {
+                    let data_seeds = def
+                        .pda
+                        .as_ref()
+                        .and_then(|pda| resolve_pda(idl, ix, scoped.scope, pda))
+                        .is_some_and(|pda| {
+                            pda.seeds.iter().chain(&pda.program).any(|seed| {
+                                matches!(
+                                    seed,
+                                    ResolvedSeed::Param {
+                                        source: SeedSource::AccountData,
+                                        ..
+                                    }
+                                )
+                            })
+                        });
+                    if data_seeds {
+                        " Required; a PDA whose seeds include account data.".to_string()
+                    } else {
+                        " Required; a PDA whose seeds cannot be resolved from the \
+                         instruction's accounts and arguments."
+                            .to_string()
+                    }
+                }
+                AccountFill::Provided if !with_data_fns.is_empty() => {
+                    format!(
+                        " Required; can be filled by {}.",
+                        with_data_fns.join(" or ")
+                    )
+                }
+                AccountFill::Provided => " Required.".to_string(),
+                AccountFill::Address(_) => " Fixed address; filled in when `None`.".to_string(),
+                AccountFill::Program => " The program itself; filled in when `None`.".to_string(),
+                AccountFill::Pda(_) => " PDA; derived when `None`.".to_string(),
+            };
+            quote! {
+                #[doc = #doc]
+                pub #name: Option<Pubkey>
+            }
+        });
+
+    let mut uses_args = false;
+    let non_pda_locals = flat.iter().zip(&fills).filter_map(|(acc, fill)| {
+        let name = format_ident!("{}", acc.name);
+        match fill {
+            AccountFill::Provided => Some(quote!(let #name = self.#name;)),
+            AccountFill::Address(bytes) => Some(quote! {
+                let #name = Some(self.#name.unwrap_or(Pubkey::new_from_array([#(#bytes),*])));
+            }),
+            AccountFill::Program => {
+                Some(quote!(let #name = Some(self.#name.unwrap_or(super::ID));))
+            }
+            AccountFill::Pda(_) => None,
+        }
+    });
+    let non_pda_locals: Vec<_> = non_pda_locals.collect();
+    let pda_locals: Vec<_> = pda_order
+        .iter()
+        .map(|&i| {
+            let AccountFill::Pda(pda) = &fills[i] else {
+                unreachable!()
+            };
+            let name = format_ident!("{}", flat[i].name);
+            let mut bound: Vec<&str> = Vec::new();
+            let bindings: Vec<_> = pda
+                .seeds
+                .iter()
+                .chain(&pda.program)
+                .filter_map(|seed| {
+                    let ResolvedSeed::Param { name, ty, source } = seed else {
+                        return None;
+                    };
+                    if bound.contains(&name.as_str()) {
+                        return None;
+                    }
+                    bound.push(name);
+                    let ident = format_ident!("{}", name);
+                    let ty_tokens = super::pda::seed_param_type(ty);
+                    Some(match source {
+                        SeedSource::Account(account) => {
+                            let account = format_ident!("{}", account);
+                            quote!(let #ident: #ty_tokens = #account.as_ref()?;)
+                        }
+                        SeedSource::Arg(path) => {
+                            uses_args = true;
+                            let path = path.iter().map(|field| format_ident!("{}", field));
+                            let value = match ty {
+                                SeedType::Int(_) | SeedType::Bool => quote!(args.#(#path).*),
+                                SeedType::String => quote!(args.#(#path).*.as_str()),
+                                SeedType::Bytes => quote!(args.#(#path).*.as_slice()),
+                                SeedType::Pubkey | SeedType::ByteArray(_) => {
+                                    quote!(&args.#(#path).*)
+                                }
+                            };
+                            quote!(let #ident: #ty_tokens = #value;)
+                        }
+                        SeedSource::AccountData => unreachable!(),
+                    })
+                })
+                .collect();
+            let find = pda.find_expr();
+            quote! {
+                let #name = self.#name.or_else(|| {
+                    #(#bindings)*
+                    Some(#find.0)
+                });
+            }
+        })
+        .collect();
+
+    let resolved_fields = flat.iter().map(|acc| {
+        let name = format_ident!("{}", acc.name);
+        let name_str = &acc.name;
+        if acc.optional {
+            quote!(#name)
+        } else {
+            quote!(#name: #name.ok_or_else(|| ::anchor_parser::__private::missing_account(#name_str))?)
+        }
+    });
+
+    let (args_param, args_doc) = if uses_args {
+        (
+            quote!(args: &#args_struct_name),
+            quote! {
+                ///
+                /// PDA seeds taken from instruction arguments are read from `args`.
+            },
+        )
+    } else {
+        (quote!(), quote!())
+    };
+    let struct_doc = format!(
+        " Accounts of the `{}` instruction, with the ones the IDL can resolve left optional.",
+        ix.name
+    );
+
+    let with_data_fns = relation_sources.iter().map(|source| {
+        let fn_name = format_ident!("with_{}_data", source.name);
+        let fills = source.related.iter().map(|(name, field)| {
+            let ident = format_ident!("{}", name);
+            let value = match source.account_ty {
+                Some(_) => {
+                    let field = format_ident!("{}", field);
+                    quote!(Some(data.#field))
+                }
+                None => quote!(::anchor_parser::AccountFields::pubkey_field(data, #field)),
+            };
+            quote! {
+                if self.#ident.is_none() {
+                    self.#ident = #value;
+                }
+            }
+        });
+        let related_list = source
+            .related
+            .iter()
+            .map(|(name, _)| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let doc = format!(
+            " Fill {related_list} from the fields of the `{}` account's decoded data.",
+            source.name
+        );
+        let data_ty = match source.account_ty {
+            Some(ty) => {
+                let ty = format_ident!("{}", ty);
+                quote!(&super::accounts::#ty)
+            }
+            None => quote!(&impl ::anchor_parser::AccountFields),
+        };
+        quote! {
+            #[doc = #doc]
+            ///
+            /// Accounts already set are kept.
+            pub fn #fn_name(mut self, data: #data_ty) -> Self {
+                #(#fills)*
+                self
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #struct_doc]
+        ///
+        /// See [`Self::resolve`].
+        #[derive(Debug, Clone, Default)]
+        pub struct #partial_name {
+            #(#fields,)*
+        }
+
+        impl #partial_name {
+            #(#with_data_fns)*
+
+            /// Fill in fixed addresses and derivable PDAs, and check that every
+            /// required account is set.
+            ///
+            /// Accounts set by the caller are kept as is. PDAs are only
+            /// derived when their seeds are constants, instruction arguments,
+            /// or other accounts of the instruction.
+            #args_doc
+            pub fn resolve(&self, #args_param) -> Result<#accounts_struct_name, std::io::Error> {
+                #(#non_pda_locals)*
+                #(#pda_locals)*
+                Ok(#accounts_struct_name {
+                    #(#resolved_fields,)*
+                })
+            }
         }
     }
 }
@@ -0,0 +1,315 @@
+use heck::ToShoutySnakeCase;
+use quote::{format_ident, quote};
+
+use crate::idl::*;
+
+/// Whether filter builders are generated, set through the `filters`
+/// feature of `anchor-parser`.
+const FILTERS: bool = cfg!(feature = "filters");
+
+// ── Borsh sizes ──────────────────────────────────────────────────────
+
+/// Maximum Borsh-serialized size of a type definition, counted like
+/// Anchor's `InitSpace`: `Option`s and enums take the size of their largest
+/// value. `None` for variable-length (strings, `bytes`, vectors) and generic
+/// types.
+pub fn borsh_space(ty_def: &IdlTypeDef, all: &[IdlTypeDef]) -> Option<usize> {
+    def_size(ty_def, all, false)
+}
+
+/// Borsh-serialized size of a type, if every value of it has the same
+/// size (`exact`), or the largest size otherwise.
+fn ty_size(ty: &IdlType, all: &[IdlTypeDef], exact: bool) -> Option<usize> {
+    match ty {
+        IdlType::Bool | IdlType::U8 | IdlType::I8 => Some(1),
+        IdlType::U16 | IdlType::I16 => Some(2),
+        IdlType::U32 | IdlType::I32 | IdlType::F32 => Some(4),
+        IdlType::U64 | IdlType::I64 | IdlType::F64 => Some(8),
+        IdlType::U128 | IdlType::I128 => Some(16),
+        IdlType::U256 | IdlType::I256 | IdlType::Pubkey => Some(32),
+        IdlType::Option(_) if exact => None,
+        IdlType::Option(inner) => Some(1 + ty_size(inner, all, exact)?),
+        IdlType::Array(inner, IdlArrayLen::Value(n)) => Some(n * ty_size(inner, all, exact)?),
+        IdlType::Defined { name, generics } if generics.is_empty() => all
+            .iter()
+            .find(|d| &d.name == name)
+            .and_then(|d| def_size(d, all, exact)),
+        _ => None,
+    }
+}
+
+fn def_size(ty_def: &IdlTypeDef, all: &[IdlTypeDef], exact: bool) -> Option<usize> {
+    if !ty_def.generics.is_empty() {
+        return None;
+    }
+    match &ty_def.ty {
+        IdlTypeDefTy::Struct { fields } => fields_size(fields.as_ref(), all, exact),
+        IdlTypeDefTy::Enum { variants } => {
+            let sizes = variants
+                .iter()
+                .map(|v| fields_size(v.fields.as_ref(), all, exact))
+                .collect::<Option<Vec<_>>>()?;
+            let max = sizes.iter().copied().max().unwrap_or(0);
+            if exact && sizes.iter().any(|&size| size != max) {
+                return None;
+            }
+            Some(1 + max)
+        }
+        IdlTypeDefTy::Type { alias } => ty_size(alias, all, exact),
+    }
+}
+
+fn fields_size(
+    fields: Option<&IdlDefinedFields>,
+    all: &[IdlTypeDef],
+    exact: bool,
+) -> Option<usize> {
+    match fields {
+        None => Some(0),
+        Some(IdlDefinedFields::Named(fs)) => fs.iter().map(|f| ty_size(&f.ty, all, exact)).sum(),
+        Some(IdlDefinedFields::Tuple(ts)) => ts.iter().map(|t| ty_size(t, all, exact)).sum(),
+    }
+}
+
+// ── Field offsets ────────────────────────────────────────────────────
+
+/// A named account field whose offset in the account data is known at
+/// compile time.
+pub struct FieldOffset<'a> {
+    pub field: &'a IdlField,
+    /// `<FIELD>_OFFSET` constant name.
+    pub const_name: proc_macro2::Ident,
+    /// Offset expression, including the discriminator.
+    pub offset: proc_macro2::TokenStream,
+}
+
+/// Offsets of the named fields of an account type, including the
+/// discriminator.
+///
+/// For bytemuck accounts every field is covered, using `offset_of!` so the
+/// offsets follow the generated `repr`. For Borsh accounts, fields are
+/// covered up to and including the first variable-size field (`Option`,
+/// string, vector, or enum with differently-sized variants); the fields
+/// after it have no fixed offset. Padding fields, whose names start with
+/// `_`, are skipped.
+pub fn field_offsets<'a>(
+    ty_def: &'a IdlTypeDef,
+    all: &[IdlTypeDef],
+    disc_len: usize,
+) -> Vec<FieldOffset<'a>> {
+    let IdlTypeDefTy::Struct {
+        fields: Some(IdlDefinedFields::Named(fields)),
+    } = &ty_def.ty
+    else {
+        return Vec::new();
+    };
+
+    let bytemuck = matches!(
+        ty_def.serialization,
+        IdlSerialization::Bytemuck | IdlSerialization::BytemuckUnsafe
+    );
+    let mut borsh_offset = Some(disc_len);
+    let mut offsets = Vec::new();
+    for field in fields {
+        let offset = if bytemuck {
+            let name = format_ident!("{}", field.name);
+            Some(quote!(#disc_len + ::core::mem::offset_of!(Self, #name)))
+        } else {
+            borsh_offset.map(|offset| quote!(#offset))
+        };
+        let Some(offset) = offset else {
+            break;
+        };
+        if !field.name.starts_with('_') {
+            offsets.push(FieldOffset {
+                field,
+                const_name: format_ident!("{}_OFFSET", field.name.to_shouty_snake_case()),
+                offset,
+            });
+        }
+        borsh_offset = borsh_offset
+            .zip(ty_size(&field.ty, all, true))
+            .map(|(offset, size)| offset + size);
+    }
+    offsets
+}
+
+// ── Generated items ──────────────────────────────────────────────────
+
+/// Little-endian bytes of a `value: &T` for the scalar field types that
+/// filters support: primitives, pubkeys, 256-bit integers and byte arrays.
+fn scalar_bytes(ty: &IdlType) -> Option<proc_macro2::TokenStream> {
+    match ty {
+        IdlType::Bool => Some(quote!(vec![*value as u8])),
+        IdlType::U8
+        | IdlType::I8
+        | IdlType::U16
+        | IdlType::I16
+        | IdlType::U32
+        | IdlType::I32
+        | IdlType::F32
+        | IdlType::U64
+        | IdlType::I64
+        | IdlType::F64
+        | IdlType::U128
+        | IdlType::I128
+        | IdlType::U256
+        | IdlType::I256 => Some(quote!(value.to_le_bytes().to_vec())),
+        IdlType::Pubkey => Some(quote!(value.to_bytes().to_vec())),
+        IdlType::Array(inner, IdlArrayLen::Value(_)) if **inner == IdlType::U8 => {
+            Some(quote!(value.to_vec()))
+        }
+        _ => None,
+    }
+}
+
+/// Whether every bit pattern is a valid value of `ty`: integers, pubkeys
+/// and byte arrays.
+fn any_bit_pattern(ty: &IdlType) -> bool {
+    match ty {
+        IdlType::U8
+        | IdlType::I8
+        | IdlType::U16
+        | IdlType::I16
+        | IdlType::U32
+        | IdlType::I32
+        | IdlType::U64
+        | IdlType::I64
+        | IdlType::U128
+        | IdlType::I128
+        | IdlType::U256
+        | IdlType::I256
+        | IdlType::Pubkey => true,
+        IdlType::Array(inner, IdlArrayLen::Value(_)) => **inner == IdlType::U8,
+        _ => false,
+    }
+}
+
+/// Field offset constants, `read_<field>` accessors and, with the `filters`
+/// feature, `memcmp` filter builders for an account type.
+pub fn gen_layout_items(
+    ty_def: &IdlTypeDef,
+    all: &[IdlTypeDef],
+    disc_len: usize,
+) -> proc_macro2::TokenStream {
+    let offsets = field_offsets(ty_def, all, disc_len);
+
+    let consts = offsets.iter().map(|o| {
+        let FieldOffset {
+            field,
+            const_name,
+            offset,
+        } = o;
+        let doc = format!(
+            " Offset of `{}` in the account data, including the discriminator.",
+            field.name
+        );
+        quote! {
+            #[doc = #doc]
+            pub const #const_name: usize = #offset;
+        }
+    });
+
+    let bytemuck = matches!(
+        ty_def.serialization,
+        IdlSerialization::Bytemuck | IdlSerialization::BytemuckUnsafe
+    );
+    // `bytemuckunsafe` accounts don't guarantee their fields are `Pod`, so
+    // only fields valid for any bytes get a reader
+    let unchecked = ty_def.serialization == IdlSerialization::BytemuckUnsafe;
+    let readers = offsets
+        .iter()
+        .filter(|o| !unchecked || o.field.ty == IdlType::Bool || any_bit_pattern(&o.field.ty));
+    let readers = readers.map(|o| {
+        let fn_name = format_ident!("read_{}", o.field.name);
+        let ty = super::common::convert_idl_type_to_tokens(&o.field.ty, false);
+        let const_name = &o.const_name;
+        let doc = format!(
+            " Read `{}` from raw account data (including discriminator prefix) \
+             without deserializing the whole account.",
+            o.field.name
+        );
+        let body = if bytemuck && o.field.ty == IdlType::Bool {
+            let name = &o.field.name;
+            quote! {
+                ::anchor_parser::__private::bytemuck_payload::<Self>(data, &Self::DISCRIMINATOR)?;
+                // SAFETY: `data` holds a whole `Self`, and any byte is a valid `u8`
+                let byte = unsafe {
+                    ::anchor_parser::__private::read_unaligned_at::<u8>(data, Self::#const_name)
+                };
+                match byte {
+                    0 => Ok(false),
+                    1 => Ok(true),
+                    _ => Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Invalid bool value {byte} for `{}`", #name),
+                    )),
+                }
+            }
+        } else if bytemuck {
+            quote! {
+                ::anchor_parser::__private::bytemuck_payload::<Self>(data, &Self::DISCRIMINATOR)?;
+                // SAFETY: `data` holds a whole `Self`, and the field's type is
+                // valid for any bytes: `bytemuck` accounts only have `Pod`
+                // fields, and `bytemuckunsafe` ones only get readers for
+                // integers, pubkeys and byte arrays
+                Ok(unsafe {
+                    ::anchor_parser::__private::read_unaligned_at::<#ty>(data, Self::#const_name)
+                })
+            }
+        } else {
+            quote! {
+                ::anchor_parser::__private::borsh_read_field(
+                    data,
+                    &Self::DISCRIMINATOR,
+                    Self::#const_name,
+                )
+            }
+        };
+        quote! {
+            #[doc = #doc]
+            pub fn #fn_name(data: &[u8]) -> Result<#ty, std::io::Error> {
+                #body
+            }
+        }
+    });
+
+    let filters = FILTERS.then(|| {
+        let field_filters = offsets.iter().filter_map(|o| {
+            let bytes = scalar_bytes(&o.field.ty)?;
+            let fn_name = format_ident!("filter_{}", o.field.name);
+            let ty = super::common::convert_idl_type_to_tokens(&o.field.ty, false);
+            let const_name = &o.const_name;
+            let doc = format!(
+                " `memcmp` filter matching accounts whose `{}` equals `value`.",
+                o.field.name
+            );
+            Some(quote! {
+                #[doc = #doc]
+                pub fn #fn_name(value: &#ty) -> ::anchor_parser::__private::RpcFilterType {
+                    ::anchor_parser::__private::memcmp_filter(Self::#const_name, &#bytes)
+                }
+            })
+        });
+        quote! {
+            /// `memcmp` filter matching accounts of this type by their
+            /// discriminator.
+            pub fn discriminator_filter() -> ::anchor_parser::__private::RpcFilterType {
+                ::anchor_parser::__private::RpcFilterType::Memcmp(
+                    ::anchor_parser::__private::Memcmp::new_base58_encoded(
+                        0,
+                        &Self::DISCRIMINATOR,
+                    ),
+                )
+            }
+
+            #(#field_filters)*
+        }
+    });
+
+    quote! {
+        #(#consts)*
+        #(#readers)*
+        #filters
+    }
+}
@@ -1,26 +1,47 @@
 mod accounts;
 mod common;
 mod constants;
+mod errors;
 mod events;
 mod instructions;
+mod layout;
+mod pda;
 mod types;
 mod utils;
 
 use quote::{format_ident, quote};
 use std::path::PathBuf;
 
+use crate::args::DeclareProgramArgs;
 use crate::idl::Idl;
 
 /// Load the IDL JSON and generate the complete program module.
-pub fn generate(name: &syn::Ident) -> Result<proc_macro2::TokenStream, Box<dyn std::error::Error>> {
-    let idl = load_idl(&name.to_string())?;
+pub fn generate(
+    args: &DeclareProgramArgs,
+) -> Result<proc_macro2::TokenStream, Box<dyn std::error::Error>> {
+    let name = &args.name;
+    let path = args.path.as_ref().map(|path| path.value());
+    let mut idl = load_idl(&name.to_string(), path.as_deref())?;
+    if let Some(program_id) = &args.program_id {
+        idl.address = program_id.value();
+    }
+    if let Some(only) = &args.only {
+        idl.select(only)?;
+    }
+    if idl.address.is_empty() {
+        return Err(format!(
+            "IDL '{name}' has no program address (legacy IDLs keep it in `metadata.address`). \
+             Pass one with `declare_program!({name}, program_id = \"...\")`."
+        )
+        .into());
+    }
 
     let mod_name = format_ident!("{}", name);
 
     // Decode program address to bytes for Pubkey::new_from_array
     let address_bytes = bs58::decode(&idl.address)
         .into_vec()
-        .map_err(|e| format!("Invalid program address '{}' in IDL: {}", idl.address, e))?;
+        .map_err(|e| format!("Invalid program address '{}': {}", idl.address, e))?;
     if address_bytes.len() != 32 {
         return Err(format!(
             "Program address must be 32 bytes, got {}",
@@ -35,6 +56,8 @@ pub fn generate(name: &syn::Ident) -> Result<proc_macro2::TokenStream, Box<dyn s
     let events_mod = events::gen_events_mod(&idl);
     let instructions_mod = instructions::gen_instructions_mod(&idl);
     let constants_mod = constants::gen_constants_mod(&idl);
+    let errors_mod = errors::gen_errors_mod(&idl);
+    let pda_mod = pda::gen_pda_mod(&idl);
     let utils_mod = utils::gen_utils_mod(&idl);
 
     let output = quote! {
@@ -49,6 +72,8 @@ pub fn generate(name: &syn::Ident) -> Result<proc_macro2::TokenStream, Box<dyn s
             #events_mod
             #instructions_mod
             #constants_mod
+            #errors_mod
+            #pda_mod
             #utils_mod
         }
     };
@@ -58,31 +83,44 @@ pub fn generate(name: &syn::Ident) -> Result<proc_macro2::TokenStream, Box<dyn s
 
 /// Find and parse the IDL JSON file.
 ///
-/// Walks up from `CARGO_MANIFEST_DIR` looking for `idls/{name}.json`.
-fn load_idl(name: &str) -> Result<Idl, Box<dyn std::error::Error>> {
+/// Walks up from `CARGO_MANIFEST_DIR` looking for `idls/{name}.json`, or for
+/// `path` when given. Absolute paths are used as is.
+fn load_idl(name: &str, path: Option<&str>) -> Result<Idl, Box<dyn std::error::Error>> {
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(
         |_| "CARGO_MANIFEST_DIR not set. This macro must be invoked during cargo build.",
     )?;
 
+    let relative = match path {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from("idls").join(format!("{name}.json")),
+    };
+
     let mut dir = PathBuf::from(&manifest_dir);
     loop {
-        let idl_path = dir.join("idls").join(format!("{name}.json"));
+        let idl_path = dir.join(&relative);
         if idl_path.exists() {
             let content = std::fs::read_to_string(&idl_path)
                 .map_err(|e| format!("Failed to read IDL file '{}': {}", idl_path.display(), e))?;
-            let idl: Idl = serde_json::from_str(&content)
+            let idl = Idl::from_json(&content)
                 .map_err(|e| format!("Failed to parse IDL file '{}': {}", idl_path.display(), e))?;
             return Ok(idl);
         }
-        if !dir.pop() {
+        if relative.is_absolute() || !dir.pop() {
             break;
         }
     }
 
+    if let Some(path) = path {
+        return Err(format!(
+            "Could not find IDL file '{path}'. Searched from '{manifest_dir}' upward."
+        )
+        .into());
+    }
     Err(format!(
         "Could not find IDL file 'idls/{name}.json'. \
          Searched from '{manifest_dir}' upward. \
-         Place your IDL JSON file at '<workspace>/idls/{name}.json'."
+         Place your IDL JSON file at '<workspace>/idls/{name}.json', \
+         or pass its location with `path = \"...\"`."
     )
     .into())
 }
@@ -0,0 +1,391 @@
+use quote::{format_ident, quote};
+
+use super::common::gen_docs;
+use crate::idl::{
+    Idl, IdlDefinedFields, IdlInstruction, IdlInstructionAccount, IdlInstructionAccountItem,
+    IdlPda, IdlSeed, IdlType, IdlTypeDefTy,
+};
+
+/// A PDA seed or program with instruction-level references resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedSeed {
+    /// Constant bytes, including accounts with a fixed address.
+    Const(Vec<u8>),
+    /// Value supplied by the caller.
+    Param {
+        name: String,
+        ty: SeedType,
+        source: SeedSource,
+    },
+}
+
+/// Where a caller-supplied seed value comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeedSource {
+    /// Instruction argument, by field path (`index` or `ix.index`).
+    Arg(Vec<String>),
+    /// Another account of the instruction.
+    Account(String),
+    /// A field of an account's data, which must be fetched.
+    AccountData,
+}
+
+/// Type of a caller-supplied seed value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeedType {
+    Pubkey,
+    /// Integer serialized in little-endian order.
+    Int(IdlType),
+    Bool,
+    String,
+    Bytes,
+    ByteArray(usize),
+}
+
+/// A PDA declared on an instruction account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedPda {
+    pub seeds: Vec<ResolvedSeed>,
+    /// `None` for the IDL's own program.
+    pub program: Option<ResolvedSeed>,
+}
+
+impl ResolvedPda {
+    /// Caller-supplied values, in seed order and without duplicates.
+    pub fn params(&self) -> Vec<(&str, &SeedType)> {
+        let mut params: Vec<(&str, &SeedType)> = Vec::new();
+        for seed in self.seeds.iter().chain(&self.program) {
+            if let ResolvedSeed::Param { name, ty, .. } = seed
+                && !params.iter().any(|(n, _)| *n == name)
+            {
+                params.push((name, ty));
+            }
+        }
+        params
+    }
+
+    /// Expression deriving `(Pubkey, u8)` from the parameters in scope.
+    pub fn find_expr(&self) -> proc_macro2::TokenStream {
+        let seeds = self.seeds.iter().map(|seed| match seed {
+            ResolvedSeed::Const(bytes) => quote!(&[#(#bytes),*]),
+            ResolvedSeed::Param { name, ty, .. } => {
+                let ident = format_ident!("{}", name);
+                match ty {
+                    SeedType::Pubkey => quote!(#ident.as_ref()),
+                    SeedType::Int(_) => quote!(&#ident.to_le_bytes()),
+                    SeedType::Bool => quote!(&[#ident as u8]),
+                    SeedType::String => quote!(#ident.as_bytes()),
+                    SeedType::Bytes => quote!(#ident),
+                    SeedType::ByteArray(_) => quote!(&#ident[..]),
+                }
+            }
+        });
+        let program = match &self.program {
+            None => quote!(&super::ID),
+            Some(ResolvedSeed::Const(bytes)) => {
+                quote!(&Pubkey::new_from_array([#(#bytes),*]))
+            }
+            Some(ResolvedSeed::Param { name, .. }) => {
+                let ident = format_ident!("{}", name);
+                quote!(#ident)
+            }
+        };
+        quote!(Pubkey::find_program_address(&[#(#seeds),*], #program))
+    }
+}
+
+/// Parameter type of a caller-supplied seed value.
+pub fn seed_param_type(ty: &SeedType) -> proc_macro2::TokenStream {
+    match ty {
+        SeedType::Pubkey => quote!(&Pubkey),
+        SeedType::Int(ty) => super::common::convert_idl_type_to_tokens(ty, false),
+        SeedType::Bool => quote!(bool),
+        SeedType::String => quote!(&str),
+        SeedType::Bytes => quote!(&[u8]),
+        SeedType::ByteArray(len) => quote!(&[u8; #len]),
+    }
+}
+
+/// A single account of an instruction, with the accounts struct it is
+/// declared in.
+pub struct ScopedAccount<'a> {
+    pub account: &'a IdlInstructionAccount,
+    /// Accounts of the enclosing accounts struct, top-level or composite,
+    /// which the account's seed paths refer to.
+    pub scope: &'a [IdlInstructionAccountItem],
+    /// Prefix of the flattened names of `scope`'s accounts: empty at the top
+    /// level, `<composite>_` inside a composite.
+    pub prefix: String,
+}
+
+/// All single accounts of an instruction, including those nested in
+/// composite accounts, in the order of
+/// [`flatten_accounts`](super::common::flatten_accounts).
+pub fn instruction_accounts(items: &[IdlInstructionAccountItem]) -> Vec<ScopedAccount<'_>> {
+    scoped_accounts(items, "")
+}
+
+fn scoped_accounts<'a>(
+    items: &'a [IdlInstructionAccountItem],
+    prefix: &str,
+) -> Vec<ScopedAccount<'a>> {
+    items
+        .iter()
+        .flat_map(|item| match item {
+            IdlInstructionAccountItem::Single(account) => vec![ScopedAccount {
+                account,
+                scope: items,
+                prefix: prefix.to_string(),
+            }],
+            IdlInstructionAccountItem::Composite(comp) => {
+                scoped_accounts(&comp.accounts, &format!("{prefix}{}_", comp.name))
+            }
+        })
+        .collect()
+}
+
+/// Resolve the seeds of a PDA declared on one of `ix`'s accounts.
+///
+/// Account seeds refer to the accounts of `scope`, the accounts struct
+/// declaring the PDA. Returns `None` if a seed cannot be expressed as a
+/// function parameter, e.g. an argument of a type Anchor does not use as a
+/// seed.
+pub fn resolve_pda(
+    idl: &Idl,
+    ix: &IdlInstruction,
+    scope: &[IdlInstructionAccountItem],
+    pda: &IdlPda,
+) -> Option<ResolvedPda> {
+    let resolve = |seed: &IdlSeed| -> Option<ResolvedSeed> {
+        match seed {
+            IdlSeed::Const(seed) => Some(ResolvedSeed::Const(seed.value.clone())),
+            IdlSeed::Arg(seed) => {
+                let mut path = seed.path.split('.');
+                let arg_name = path.next()?;
+                let arg = ix.args.iter().find(|arg| arg.name == arg_name)?;
+                let ty = resolve_field_path(idl, &arg.ty, path)?;
+                Some(ResolvedSeed::Param {
+                    name: seed.path.replace('.', "_"),
+                    ty: seed_type(idl, ty)?,
+                    source: SeedSource::Arg(seed.path.split('.').map(String::from).collect()),
+                })
+            }
+            IdlSeed::Account(seed) => match seed.path.split_once('.') {
+                None => {
+                    let address = scope.iter().find_map(|item| match item {
+                        IdlInstructionAccountItem::Single(acc) if acc.name == seed.path => {
+                            acc.address.as_ref()
+                        }
+                        _ => None,
+                    });
+                    match address {
+                        Some(address) => {
+                            Some(ResolvedSeed::Const(bs58::decode(address).into_vec().ok()?))
+                        }
+                        None => Some(ResolvedSeed::Param {
+                            name: seed.path.clone(),
+                            ty: SeedType::Pubkey,
+                            source: SeedSource::Account(seed.path.clone()),
+                        }),
+                    }
+                }
+                Some((_, fields)) => {
+                    let account_ty = IdlType::Defined {
+                        name: seed.account.clone()?,
+                        generics: Vec::new(),
+                    };
+                    let ty = resolve_field_path(idl, &account_ty, fields.split('.'))?;
+                    Some(ResolvedSeed::Param {
+                        name: seed.path.replace('.', "_"),
+                        ty: seed_type(idl, ty)?,
+                        source: SeedSource::AccountData,
+                    })
+                }
+            },
+        }
+    };
+
+    let seeds = pda.seeds.iter().map(resolve).collect::<Option<Vec<_>>>()?;
+    let program = match &pda.program {
+        Some(program) => match resolve(program)? {
+            ResolvedSeed::Const(bytes) if bytes.len() == 32 => Some(ResolvedSeed::Const(bytes)),
+            param @ ResolvedSeed::Param {
+                ty: SeedType::Pubkey,
+                ..
+            } => Some(param),
+            _ => return None,
+        },
+        None => None,
+    };
+    Some(ResolvedPda { seeds, program })
+}
+
+/// Follow `fields` through the struct types of the IDL.
+fn resolve_field_path<'a, 'b>(
+    idl: &'a Idl,
+    mut ty: &'a IdlType,
+    fields: impl Iterator<Item = &'b str>,
+) -> Option<&'a IdlType> {
+    for field in fields {
+        let IdlType::Defined { name, .. } = ty else {
+            return None;
+        };
+        let ty_def = idl.types.iter().find(|ty| ty.name == *name)?;
+        let IdlTypeDefTy::Struct {
+            fields: Some(IdlDefinedFields::Named(defined)),
+        } = &ty_def.ty
+        else {
+            return None;
+        };
+        ty = &defined.iter().find(|f| f.name == field)?.ty;
+    }
+    Some(ty)
+}
+
+fn seed_type(idl: &Idl, ty: &IdlType) -> Option<SeedType> {
+    match ty {
+        IdlType::Pubkey => Some(SeedType::Pubkey),
+        IdlType::U8
+        | IdlType::I8
+        | IdlType::U16
+        | IdlType::I16
+        | IdlType::U32
+        | IdlType::I32
+        | IdlType::U64
+        | IdlType::I64
+        | IdlType::U128
+        | IdlType::I128 => Some(SeedType::Int(ty.clone())),
+        IdlType::Bool => Some(SeedType::Bool),
+        IdlType::String => Some(SeedType::String),
+        IdlType::Bytes => Some(SeedType::Bytes),
+        IdlType::Vec(inner) if **inner == IdlType::U8 => Some(SeedType::Bytes),
+        IdlType::Array(inner, crate::idl::IdlArrayLen::Value(len)) if **inner == IdlType::U8 => {
+            Some(SeedType::ByteArray(*len))
+        }
+        // Type aliases, e.g. `type Index = u16`
+        IdlType::Defined { name, .. } => match &idl.types.iter().find(|t| t.name == *name)?.ty {
+            IdlTypeDefTy::Type { alias } => seed_type(idl, alias),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Render a seed for doc comments.
+fn describe_seed(seed: &ResolvedSeed) -> String {
+    match seed {
+        ResolvedSeed::Const(bytes)
+            if !bytes.is_empty() && bytes.iter().all(|b| b.is_ascii_graphic() && *b != b'`') =>
+        {
+            format!("`b\"{}\"`", String::from_utf8_lossy(bytes))
+        }
+        ResolvedSeed::Const(bytes) if bytes.len() == 32 => {
+            format!("`{}`", bs58::encode(bytes).into_string())
+        }
+        ResolvedSeed::Const(bytes) => format!("`{bytes:?}`"),
+        ResolvedSeed::Param { name, .. } => format!("`{name}`"),
+    }
+}
+
+/// Generate the `pda` module with a `find_<account>` function per PDA
+/// declared on instruction accounts.
+///
+/// Accounts sharing a name and seeds across instructions get a single
+/// function. When instructions disagree on the seeds of an account, each
+/// variant is named after the first instruction using it:
+/// `find_<instruction>_<account>`, or `find_<instruction>_<composite>_<account>`
+/// for accounts in a composite, with a numeric suffix if that is still taken.
+pub fn gen_pda_mod(idl: &Idl) -> proc_macro2::TokenStream {
+    // (account name, first instruction, composite prefix, pda), deduplicated
+    let mut pdas: Vec<(&str, &str, String, ResolvedPda)> = Vec::new();
+    for ix in &idl.instructions {
+        for scoped in instruction_accounts(&ix.accounts) {
+            let acc = scoped.account;
+            let Some(pda) = acc
+                .pda
+                .as_ref()
+                .and_then(|pda| resolve_pda(idl, ix, scoped.scope, pda))
+            else {
+                continue;
+            };
+            if !pdas
+                .iter()
+                .any(|(name, _, _, existing)| *name == acc.name && *existing == pda)
+            {
+                pdas.push((&acc.name, &ix.name, scoped.prefix, pda));
+            }
+        }
+    }
+
+    let ambiguous: Vec<bool> = pdas
+        .iter()
+        .map(|(name, ..)| pdas.iter().filter(|(n, ..)| n == name).count() > 1)
+        .collect();
+    // Unambiguous names are reserved first, so disambiguated ones avoid them
+    let mut taken: Vec<String> = pdas
+        .iter()
+        .zip(&ambiguous)
+        .filter(|(_, ambiguous)| !**ambiguous)
+        .map(|((name, ..), _)| format!("find_{name}"))
+        .collect();
+    let fn_names: Vec<String> = pdas
+        .iter()
+        .zip(&ambiguous)
+        .map(|((name, ix_name, prefix, _), &ambiguous)| {
+            if !ambiguous {
+                return format!("find_{name}");
+            }
+            let mut fn_name = format!("find_{ix_name}_{name}");
+            if taken.contains(&fn_name) {
+                fn_name = format!("find_{ix_name}_{prefix}{name}");
+            }
+            let base = fn_name.clone();
+            let mut n = 2;
+            while taken.contains(&fn_name) {
+                fn_name = format!("{base}_{n}");
+                n += 1;
+            }
+            taken.push(fn_name.clone());
+            fn_name
+        })
+        .collect();
+
+    let fns = pdas.iter().enumerate().map(|(i, (name, ix_name, _, pda))| {
+        let fn_name = format_ident!("{}", fn_names[i]);
+
+        let mut docs = vec![format!("PDA of the `{name}` account.")];
+        if ambiguous[i] {
+            docs.push(format!("As derived by the `{ix_name}` instruction."));
+        }
+        docs.push(String::new());
+        let seeds: Vec<_> = pda.seeds.iter().map(describe_seed).collect();
+        docs.push(format!("Seeds: {}.", seeds.join(", ")));
+        if let Some(program) = &pda.program {
+            docs.push(format!("Derived under program {}.", describe_seed(program)));
+        }
+        let docs = gen_docs(&docs);
+
+        let params = pda.params().into_iter().map(|(name, ty)| {
+            let ident = format_ident!("{}", name);
+            let ty = seed_param_type(ty);
+            quote!(#ident: #ty)
+        });
+        let find = pda.find_expr();
+
+        quote! {
+            #docs
+            pub fn #fn_name(#(#params),*) -> (Pubkey, u8) {
+                #find
+            }
+        }
+    });
+
+    quote! {
+        /// Program derived addresses declared in the IDL.
+        pub mod pda {
+            use ::anchor_parser::__private::*;
+
+            #(#fns)*
+        }
+    }
+}
@@ -3,10 +3,12 @@ use quote::{format_ident, quote};
 use super::common::gen_discriminator;
 use crate::idl::Idl;
 
-/// Generate the `utils` module with `Event` and `Account` wrapper enums.
+/// Generate the `utils` module with `Event` and `Account` wrapper enums and
+/// the `parse_transaction` decoder.
 pub fn gen_utils_mod(idl: &Idl) -> proc_macro2::TokenStream {
     let event_enum = gen_event_enum(idl);
     let account_enum = gen_account_enum(idl);
+    let parse_transaction = gen_parse_transaction(idl);
 
     quote! {
         /// Parser utilities.
@@ -19,6 +21,7 @@ pub fn gen_utils_mod(idl: &Idl) -> proc_macro2::TokenStream {
 
             #event_enum
             #account_enum
+            #parse_transaction
         }
     }
 }
@@ -37,45 +40,46 @@ fn gen_event_enum(idl: &Idl) -> proc_macro2::TokenStream {
         })
         .collect();
 
-    let from_logs_arms: Vec<_> = idl
+    let decode_arms: Vec<_> = idl
         .events
         .iter()
         .map(|ev| {
             let name = format_ident!("{}", ev.name);
-            let disc = gen_discriminator(&ev.discriminator);
             let disc_len = ev.discriminator.len();
             quote! {
-                {
-                    const __DISC: [u8; #disc_len] = #disc;
-                    if data.len() >= #disc_len && data[..#disc_len] == __DISC {
-                        if let Ok(inner) = ::anchor_parser::__private::BorshDeserialize::try_from_slice(
-                            &data[#disc_len..],
-                        ) {
-                            return Some(Event::#name(inner));
-                        }
+                if data.len() >= #disc_len && data[..#disc_len] == #name::DISCRIMINATOR {
+                    if let Ok(inner) = ::anchor_parser::__private::BorshDeserialize::try_from_slice(
+                        &data[#disc_len..],
+                    ) {
+                        return Some(Event::#name(inner));
                     }
                 }
             }
         })
         .collect();
 
-    let from_cpi_arms: Vec<_> = idl
+    let known_discriminator_checks: Vec<_> = idl
+        .events
+        .iter()
+        .map(|ev| {
+            let name = format_ident!("{}", ev.name);
+            quote!(::anchor_parser::logs::ProgramDataDecoder::prefix_matches(prefix, &#name::DISCRIMINATOR))
+        })
+        .collect();
+
+    let try_decode_arms: Vec<_> = idl
         .events
         .iter()
         .map(|ev| {
             let name = format_ident!("{}", ev.name);
-            let disc = gen_discriminator(&ev.discriminator);
             let disc_len = ev.discriminator.len();
             quote! {
-                {
-                    const __DISC: [u8; #disc_len] = #disc;
-                    if data.len() >= #disc_len && data[..#disc_len] == __DISC {
-                        if let Ok(inner) = ::anchor_parser::__private::BorshDeserialize::try_from_slice(
-                            &data[#disc_len..],
-                        ) {
-                            return Some(Event::#name(inner));
-                        }
-                    }
+                if data.len() >= #disc_len && data[..#disc_len] == #name::DISCRIMINATOR {
+                    return ::anchor_parser::__private::BorshDeserialize::try_from_slice(&data[#disc_len..])
+                        .map(Event::#name)
+                        .map_err(|error| {
+                            ::anchor_parser::event::EventDecodeError::deserialize(index, data, #disc_len, error)
+                        });
                 }
             }
         })
@@ -89,10 +93,16 @@ fn gen_event_enum(idl: &Idl) -> proc_macro2::TokenStream {
         }
 
         impl Event {
+            /// Decode an event from its discriminator and Borsh payload.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                #(#decode_arms)*
+                None
+            }
+
             /// Parse all program events from `emit!` log lines.
             ///
             /// Matches every `"Program data: <base64>"` line regardless of
-            /// which program emitted it.
+            /// which program emitted it; see [`Self::from_program_logs`].
             pub fn from_logs<T, I>(logs: T) -> Vec<Self>
             where
                 T: IntoIterator<Item = I>,
@@ -102,8 +112,82 @@ fn gen_event_enum(idl: &Idl) -> proc_macro2::TokenStream {
                     .filter_map(|log| {
                         let data_str = log.as_ref().strip_prefix("Program data: ")?;
                         let data = ::anchor_parser::__private::base64_decode(data_str)?;
-                        #(#from_logs_arms)*
-                        None
+                        Self::decode(&data)
+                    })
+                    .collect()
+            }
+
+            /// Lazily parse program events from `emit!` log lines.
+            ///
+            /// Like [`Self::from_logs`], but decodes one line at a time into a
+            /// single reused buffer, and only base64-decodes a line in full
+            /// once its leading bytes match a known event discriminator.
+            pub fn iter_logs<T, I>(logs: T) -> impl Iterator<Item = Self>
+            where
+                T: IntoIterator<Item = I>,
+                I: AsRef<str>,
+            {
+                let mut decoder = ::anchor_parser::logs::ProgramDataDecoder::new();
+                logs.into_iter().filter_map(move |log| {
+                    Self::decode(decoder.decode(log.as_ref(), Self::is_known_discriminator)?)
+                })
+            }
+
+            /// Whether `prefix` may start with a known event discriminator.
+            fn is_known_discriminator(prefix: &[u8]) -> bool {
+                #(#known_discriminator_checks)||*
+            }
+
+            /// Parse all program events from `emit!` log lines, reporting the
+            /// lines that could not be decoded.
+            ///
+            /// Yields one result per `"Program data: <base64>"` line: the
+            /// decoded event,
+            /// [`EventDecodeError::Deserialize`](::anchor_parser::event::EventDecodeError::Deserialize)
+            /// when the payload of a known event fails to deserialize, or
+            /// [`EventDecodeError::Unknown`](::anchor_parser::event::EventDecodeError::Unknown)
+            /// when the discriminator matches no event in the IDL.
+            pub fn try_from_logs<T, I>(
+                logs: T,
+            ) -> Vec<Result<Self, ::anchor_parser::event::EventDecodeError>>
+            where
+                T: IntoIterator<Item = I>,
+                I: AsRef<str>,
+            {
+                logs.into_iter()
+                    .enumerate()
+                    .filter_map(|(index, log)| {
+                        let data_str = log.as_ref().strip_prefix("Program data: ")?;
+                        let data = ::anchor_parser::__private::base64_decode(data_str)?;
+                        Some(Self::try_decode_at(index, &data))
+                    })
+                    .collect()
+            }
+
+            /// Decode an event, reporting an unknown discriminator or a
+            /// payload that fails to deserialize.
+            fn try_decode_at(
+                index: usize,
+                data: &[u8],
+            ) -> Result<Self, ::anchor_parser::event::EventDecodeError> {
+                #(#try_decode_arms)*
+                Err(::anchor_parser::event::EventDecodeError::unknown(index, data))
+            }
+
+            /// Parse all events emitted by this program through `emit!`.
+            ///
+            /// Follows the `Program <id> invoke [n]` / `success` / `failed`
+            /// lines and only decodes `"Program data: <base64>"` lines written
+            /// while [`ID`](super::ID) is on top of the invoke stack. Each
+            /// event carries its invoke depth and top-level instruction index.
+            pub fn from_program_logs<T, I>(logs: T) -> Vec<::anchor_parser::event::LoggedEvent<Self>>
+            where
+                T: IntoIterator<Item = I>,
+                I: AsRef<str>,
+            {
+                ::anchor_parser::logs::program_data(super::ID, logs)
+                    .filter_map(|(context, data)| {
+                        Some(::anchor_parser::event::LoggedEvent::new(Self::decode(&data)?, context))
                     })
                     .collect()
             }
@@ -116,24 +200,63 @@ fn gen_event_enum(idl: &Idl) -> proc_macro2::TokenStream {
             ///
             /// The expected binary layout is:
             /// `[8-byte CPI event tag][8-byte event discriminator][borsh payload]`
+            ///
+            /// Entries that do not start with Anchor's
+            /// [`EVENT_IX_TAG`](::anchor_parser::event::EVENT_IX_TAG) are
+            /// skipped. Use [`Self::from_cpi_instruction`] to also check the
+            /// program ID and event authority.
             pub fn from_cpi_logs<T, I>(logs: T) -> Vec<Self>
             where
                 T: IntoIterator<Item = I>,
                 I: AsRef<str>,
             {
-                const CPI_TAG_LEN: usize = 8;
                 logs.into_iter()
                     .filter_map(|log| {
                         let raw = ::anchor_parser::__private::bs58_decode(log.as_ref())?;
-                        if raw.len() < CPI_TAG_LEN {
-                            return None;
-                        }
-                        let data = &raw[CPI_TAG_LEN..];
-                        #(#from_cpi_arms)*
-                        None
+                        Self::decode(::anchor_parser::event::strip_event_ix_tag(&raw)?)
+                    })
+                    .collect()
+            }
+
+            /// Parse all program events from `emit_cpi!` inner instruction
+            /// data, reporting the entries that could not be decoded.
+            ///
+            /// Yields one result per entry that starts with Anchor's event
+            /// tag; see [`Self::try_from_logs`] for the possible errors.
+            pub fn try_from_cpi_logs<T, I>(
+                logs: T,
+            ) -> Vec<Result<Self, ::anchor_parser::event::EventDecodeError>>
+            where
+                T: IntoIterator<Item = I>,
+                I: AsRef<str>,
+            {
+                logs.into_iter()
+                    .enumerate()
+                    .filter_map(|(index, log)| {
+                        let raw = ::anchor_parser::__private::bs58_decode(log.as_ref())?;
+                        let data = ::anchor_parser::event::strip_event_ix_tag(&raw)?;
+                        Some(Self::try_decode_at(index, data))
                     })
                     .collect()
             }
+
+            /// Decode a program event from an `emit_cpi!` inner instruction.
+            ///
+            /// Returns `None` unless the instruction targets [`ID`](super::ID),
+            /// is signed by the program's
+            /// [`event_authority`](super::events::event_authority), and its
+            /// data starts with Anchor's event tag and a known event
+            /// discriminator.
+            pub fn from_cpi_instruction(
+                program_id: &Pubkey,
+                accounts: &[Pubkey],
+                data: &[u8],
+            ) -> Option<Self> {
+                if !super::events::is_event_cpi(program_id, accounts) {
+                    return None;
+                }
+                Self::decode(::anchor_parser::event::strip_event_ix_tag(data)?)
+            }
         }
     }
 }
@@ -172,6 +295,24 @@ fn gen_account_enum(idl: &Idl) -> proc_macro2::TokenStream {
         })
         .collect();
 
+    let pubkey_field_arms: Vec<_> = idl
+        .accounts
+        .iter()
+        .map(|acc| {
+            let name = format_ident!("{}", acc.name);
+            quote!(Account::#name(inner) => ::anchor_parser::AccountFields::pubkey_field(inner, name))
+        })
+        .collect();
+
+    let serialize_arms: Vec<_> = idl
+        .accounts
+        .iter()
+        .map(|acc| {
+            let name = format_ident!("{}", acc.name);
+            quote!(Account::#name(inner) => ::anchor_parser::AccountSerialize::try_serialize(inner, writer))
+        })
+        .collect();
+
     quote! {
         /// Enum wrapping all program account types.
         #[derive(Debug, Clone)]
@@ -189,5 +330,160 @@ fn gen_account_enum(idl: &Idl) -> proc_macro2::TokenStream {
                 ))
             }
         }
+
+        impl ::anchor_parser::AccountFields for Account {
+            fn pubkey_field(&self, name: &str) -> Option<Pubkey> {
+                match self {
+                    #(#pubkey_field_arms,)*
+                }
+            }
+        }
+
+        impl ::anchor_parser::AccountSerialize for Account {
+            fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+                match self {
+                    #(#serialize_arms,)*
+                }
+            }
+        }
+    }
+}
+
+fn gen_parse_transaction(idl: &Idl) -> proc_macro2::TokenStream {
+    if idl.instructions.is_empty() {
+        return quote!();
+    }
+
+    // `emit_cpi!` events are only attached when the program declares events
+    let (events_field, events_init, attach_event) = if idl.events.is_empty() {
+        (quote!(), quote!(), quote!())
+    } else {
+        (
+            quote! {
+                /// Events emitted through `emit_cpi!` while executing this
+                /// instruction.
+                pub events: Vec<Event>,
+            },
+            quote!(events: Vec::new(),),
+            quote! {
+                if ::anchor_parser::event::strip_event_ix_tag(ix.data).is_some() {
+                    let authority = ix
+                        .accounts
+                        .first()
+                        .and_then(|&index| account_keys.get(index as usize));
+                    let event = authority.and_then(|authority| {
+                        Event::from_cpi_instruction(&ix.program_id, &[*authority], ix.data)
+                    });
+                    // Attach to the innermost program instruction that invoked
+                    // this self-CPI
+                    let parent = parsed.iter_mut().rev().find(|parent| {
+                        let (index, stack_height) = match parent {
+                            Ok(parent) => (parent.index, parent.stack_height),
+                            Err(err) => (err.index, err.stack_height),
+                        };
+                        index == ix.index && (ix.stack_height == 0 || stack_height < ix.stack_height)
+                    });
+                    if let (Some(Ok(parent)), Some(event)) = (parent, event) {
+                        parent.events.push(event);
+                    }
+                    continue;
+                }
+            },
+        )
+    };
+
+    quote! {
+        /// An instruction targeting this program, decoded from a transaction.
+        #[derive(Debug, Clone)]
+        pub struct ParsedInstruction {
+            /// Index of the top-level instruction (or of the one that invoked
+            /// this CPI).
+            pub index: u8,
+            /// Position within the top-level instruction's inner instructions,
+            /// or `None` for a top-level instruction.
+            pub inner_index: Option<usize>,
+            /// Invocation stack height (`1` for top-level, `0` if unknown).
+            pub stack_height: u8,
+            /// Decoded instruction arguments.
+            pub instruction: super::instructions::Instruction,
+            /// Decoded instruction accounts.
+            pub accounts: super::instructions::InstructionAccounts,
+            /// Accounts passed after the instruction's own accounts.
+            pub remaining_accounts: Vec<Pubkey>,
+            #events_field
+        }
+
+        /// Decode every instruction of a transaction that targets [`ID`](super::ID).
+        ///
+        /// Walks top-level instructions and the CPIs recorded in
+        /// `inner_instructions` (from the transaction status meta). For v0
+        /// transactions, `loaded_addresses` must hold the addresses resolved
+        /// from lookup tables; pass `LoadedAddresses::default()` otherwise.
+        ///
+        /// `emit_cpi!` self-CPIs are not returned as instructions; their
+        /// events are attached to the instruction that emitted them.
+        ///
+        /// Instructions targeting this program that cannot be decoded, e.g.
+        /// ones added after the IDL, are returned as errors in place, so the
+        /// rest of the transaction is still decoded.
+        pub fn parse_transaction(
+            tx: &VersionedTransaction,
+            loaded_addresses: &LoadedAddresses,
+            inner_instructions: &[::anchor_parser::transaction::InnerInstructions],
+        ) -> Vec<Result<ParsedInstruction, ::anchor_parser::transaction::InstructionDecodeError>> {
+            let account_keys = ::anchor_parser::transaction::account_keys(&tx.message, loaded_addresses);
+            let mut parsed: Vec<
+                Result<ParsedInstruction, ::anchor_parser::transaction::InstructionDecodeError>,
+            > = Vec::new();
+
+            for ix in ::anchor_parser::transaction::instructions(
+                &tx.message,
+                &account_keys,
+                inner_instructions,
+            ) {
+                if ix.program_id != super::ID {
+                    continue;
+                }
+                #attach_event
+
+                let decoded = super::instructions::Instruction::decode(ix.data).and_then(|instruction| {
+                    let accounts = super::instructions::InstructionAccounts::from_account_keys(
+                        &instruction,
+                        &ix.program_id,
+                        &account_keys,
+                        ix.accounts,
+                    )?;
+                    Ok((instruction, accounts))
+                });
+                let (instruction, accounts) = match decoded {
+                    Ok(decoded) => decoded,
+                    Err(error) => {
+                        parsed.push(Err(::anchor_parser::transaction::InstructionDecodeError::new(
+                            &ix, error,
+                        )));
+                        continue;
+                    }
+                };
+                let remaining_accounts = ix
+                    .accounts
+                    .get(accounts.accounts_len()..)
+                    .unwrap_or(&[])
+                    .iter()
+                    .filter_map(|&index| account_keys.get(index as usize).copied())
+                    .collect();
+
+                parsed.push(Ok(ParsedInstruction {
+                    index: ix.index,
+                    inner_index: ix.inner_index,
+                    stack_height: ix.stack_height,
+                    instruction,
+                    accounts,
+                    remaining_accounts,
+                    #events_init
+                }));
+            }
+
+            parsed
+        }
     }
 }
@@ -1,7 +1,9 @@
 extern crate proc_macro;
 
+mod args;
 mod codegen;
-mod idl;
+
+use anchor_parser_idl as idl;
 
 use proc_macro::TokenStream;
 use quote::quote;
@@ -9,6 +11,21 @@ use quote::quote;
 /// Generate a complete program module from an Anchor IDL JSON file.
 ///
 /// Searches for `idls/{name}.json` by walking up from `CARGO_MANIFEST_DIR`.
+/// Legacy (pre-0.30) IDLs are detected and converted.
+///
+/// # Arguments
+///
+/// The first argument names the generated module. It may be followed by:
+///
+/// - **`path = "..."`** — IDL file to load instead of `idls/{name}.json`.
+///   Relative paths are searched from `CARGO_MANIFEST_DIR` upward.
+/// - **`program_id = "..."`** — program ID to use instead of the IDL's
+///   address, e.g. a devnet or fork deployment. Required for legacy IDLs
+///   without `metadata.address`.
+/// - **`only(...)`** — generate only the listed items, plus the type
+///   definitions they reference: `only(accounts(LbPair), events(Swap))`.
+///   Kinds are `types`, `accounts`, `events`, `instructions`, `constants`
+///   and `errors`; a bare kind keeps all of its items, an omitted one none.
 ///
 /// # Generated items
 ///
@@ -16,14 +33,22 @@ use quote::quote;
 /// - **`types`** — shared structs, enums, and type aliases
 /// - **`accounts`** — account structs with discriminator and deserialization
 /// - **`events`** — event structs with `from_logs` / `from_cpi_logs` parsers
-/// - **`instructions`** — builder functions → [`Instruction`](solana_sdk::instruction::Instruction)
+/// - **`instructions`** — builder functions → [`Instruction`](solana_sdk::instruction::Instruction),
+///   `Instruction` enum decoding instruction data
 /// - **`constants`** — program constants
-/// - **`utils`** — `Event` / `Account` wrapper enums
+/// - **`errors`** — `ErrorCode` enum of custom program errors
+/// - **`pda`** — `find_<account>` functions deriving the PDAs declared in the IDL
+/// - **`utils`** — `Event` / `Account` wrapper enums and `parse_transaction`
 ///
 /// # Example
 ///
 /// ```ignore
 /// anchor_parser::declare_program!(my_program);
+/// anchor_parser::declare_program!(
+///     my_program_devnet,
+///     path = "vendor/my_program-v0.9.json",
+///     program_id = "11111111111111111111111111111111",
+/// );
 ///
 /// // Use generated types:
 /// use my_program::accounts::MyAccount;
@@ -32,8 +57,8 @@ use quote::quote;
 /// ```
 #[proc_macro]
 pub fn declare_program(input: TokenStream) -> TokenStream {
-    let name = syn::parse_macro_input!(input as syn::Ident);
-    match codegen::generate(&name) {
+    let args = syn::parse_macro_input!(input as args::DeclareProgramArgs);
+    match codegen::generate(&args) {
         Ok(tokens) => tokens.into(),
         Err(err) => {
             let msg = err.to_string();